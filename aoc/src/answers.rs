@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+
+use {Error, Result, column, read_file};
+
+/// A short stable fingerprint of some puzzle input (64-bit FNV-1a, as hex).
+pub fn digest(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325u64, |acc, b| {
+        (acc ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+type Key = (u32, u32, String);
+
+/// Known correct answers keyed by day, part and input digest.
+///
+/// Stored one per line as `<day> <part> <digest> <answer>`.
+/// Blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: BTreeMap<Key, String>,
+}
+
+impl Answers {
+    /// Load answers from `path`. A missing file is an empty database.
+    pub fn load(path: &str) -> Result<Self> {
+        match fs::metadata(path) {
+            Err(ref err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            _ => {},
+        }
+        Self::parse(&read_file(path)?)
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut entries = BTreeMap::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let words: Vec<&str> = line.splitn(4, ' ').collect();
+            if words.len() != 4 {
+                return Err(Error::parse(i + 1, 1, "expected <day> <part> <digest> <answer>"));
+            }
+            let number = |s: &str| s.parse::<u32>().map_err(|_| {
+                Error::parse(i + 1, column(line, s), format!("expected a number: {}", s))
+            });
+            let key = (number(words[0])?, number(words[1])?, words[2].to_owned());
+            entries.insert(key, words[3].to_owned());
+        }
+        Ok(Self{entries})
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        fs::write(path, self.render()).map_err(|err| Error::io(path, err))
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (&(day, part, ref digest), answer) in self.entries.iter() {
+            out.push_str(&format!("{} {} {} {}\n", day, part, digest, answer));
+        }
+        out
+    }
+
+    pub fn get(&self, day: u32, part: u32, digest: &str) -> Option<&str> {
+        self.entries.get(&(day, part, digest.to_owned())).map(|s| s.as_str())
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, digest: &str, answer: String) {
+        self.entries.insert((day, part, digest.to_owned()), answer);
+    }
+}
+
+#[test]
+fn roundtrip() {
+    let mut a = Answers::parse("# comment\n7 1 00ff tknk\n\n7 2 00ff 60\n").unwrap();
+    assert_eq!(Some("tknk"), a.get(7, 1, "00ff"));
+    assert_eq!(None, a.get(7, 1, "00fe"));
+    a.insert(10, 2, "abcd", "a2582a3a".to_owned());
+    let b = Answers::parse(&a.render()).unwrap();
+    assert_eq!(Some("a2582a3a"), b.get(10, 2, "abcd"));
+    assert!(Answers::parse("7 x 00ff 1").is_err());
+}
+
+#[test]
+fn digest_is_stable() {
+    assert_eq!("cbf29ce484222325", digest(""));
+    assert_eq!("af63dc4c8601ec8c", digest("a"));
+}
@@ -0,0 +1,180 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use {Json, Result};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Answer::Int(x) => write!(f, "{}", x),
+            Answer::Str(ref s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl<'a> From<&'a Answer> for Json {
+    fn from(answer: &'a Answer) -> Self {
+        match *answer {
+            Answer::Int(x) => Json::Int(x),
+            Answer::Str(ref s) => Json::Str(s.clone()),
+        }
+    }
+}
+
+/// Anything a part function may return.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer>;
+}
+
+impl IntoAnswer for i64 {
+    fn into_answer(self) -> Result<Answer> { Ok(Answer::Int(self)) }
+}
+
+impl IntoAnswer for String {
+    fn into_answer(self) -> Result<Answer> { Ok(Answer::Str(self)) }
+}
+
+impl<T> IntoAnswer for Result<T>
+    where T: IntoAnswer
+{
+    fn into_answer(self) -> Result<Answer> { self.and_then(IntoAnswer::into_answer) }
+}
+
+/// The outcome of running one part once.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub answer: Answer,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+type Solver = Box<dyn Fn(&str) -> Result<Run> + Send + Sync>;
+
+/// One part of a day: parses the raw input and solves it.
+pub struct Part {
+    pub number: u32,
+    solve: Solver,
+}
+
+impl Part {
+    pub fn run(&self, input: &str) -> Result<Run> {
+        (self.solve)(input)
+    }
+}
+
+type ToolFn = Box<dyn Fn(&[String]) -> Result<String> + Send + Sync>;
+
+/// An extra command a day offers besides its parts, such as a visualiser.
+pub struct Tool {
+    pub name: String,
+    run: ToolFn,
+}
+
+impl Tool {
+    /// Run with the command line arguments after the day number.
+    pub fn run(&self, args: &[String]) -> Result<String> {
+        (self.run)(args)
+    }
+}
+
+/// A day's puzzle with its registered parts and tools.
+pub struct Day {
+    pub number: u32,
+    parts: Vec<Part>,
+    tools: Vec<Tool>,
+}
+
+impl Day {
+    pub fn new(number: u32) -> Self {
+        Self{
+            number,
+            parts: Vec::new(),
+            tools: Vec::new(),
+        }
+    }
+
+    /// Register part `number` as `solve(parse(input))`.
+    pub fn part<I,A,P,S>(mut self, number: u32, parse: P, solve: S) -> Self
+        where A: IntoAnswer,
+              P: Fn(&str) -> Result<I> + Send + Sync + 'static,
+              S: Fn(I) -> A + Send + Sync + 'static
+    {
+        self.parts.push(Part{
+            number,
+            solve: Box::new(move |s| {
+                let start = Instant::now();
+                let x = parse(s)?;
+                let parsed = Instant::now();
+                let answer = solve(x).into_answer()?;
+                Ok(Run{
+                    answer,
+                    parse_time: parsed - start,
+                    solve_time: parsed.elapsed(),
+                })
+            }),
+        });
+        self
+    }
+
+    /// Register a tool, run from the command line as `aoc <name> <day> [args...]`.
+    pub fn tool<F>(mut self, name: &str, run: F) -> Self
+        where F: Fn(&[String]) -> Result<String> + Send + Sync + 'static
+    {
+        self.tools.push(Tool{
+            name: name.to_owned(),
+            run: Box::new(run),
+        });
+        self
+    }
+
+    pub fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+
+    pub fn find_part(&self, number: u32) -> Option<&Part> {
+        self.parts.iter().find(|p| p.number == number)
+    }
+
+    pub fn tools(&self) -> &[Tool] {
+        &self.tools
+    }
+
+    pub fn find_tool(&self, name: &str) -> Option<&Tool> {
+        self.tools.iter().find(|t| t.name == name)
+    }
+
+    /// Check worked examples given as `(input, part, expected answer)`,
+    /// panicking with every mismatch. Every registered part needs at least one.
+    pub fn assert_examples(&self, examples: &[(&str, u32, &str)]) {
+        let mut failures = Vec::new();
+        for part in self.parts.iter() {
+            if !examples.iter().any(|&(_, n, _)| n == part.number) {
+                failures.push(format!("part {} has no examples", part.number));
+            }
+        }
+        for &(input, n, want) in examples {
+            let part = match self.find_part(n) {
+                Some(part) => part,
+                None => {
+                    failures.push(format!("part {} is not registered", n));
+                    continue;
+                },
+            };
+            match part.run(input).map(|r| r.answer) {
+                Ok(ref got) if got.to_string() == want => {},
+                Ok(got) => failures.push(format!("part {} on {:?}: expected {}, got {}", n, input, want, got)),
+                Err(err) => failures.push(format!("part {} on {:?}: expected {}, got error: {}", n, input, want, err)),
+            }
+        }
+        if !failures.is_empty() {
+            panic!("day {} examples failed:\n  {}", self.number, failures.join("\n  "));
+        }
+    }
+}
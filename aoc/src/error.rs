@@ -0,0 +1,118 @@
+use std::error;
+use std::fmt;
+use std::io;
+
+use Json;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Reading input from `path` failed.
+    Io { path: String, err: io::Error },
+    /// Malformed input at a 1-based `line` and `column`.
+    Parse { line: usize, column: usize, msg: String },
+    /// The puzzle reached a state the solver does not allow for.
+    InvalidState(String),
+    /// Well-formed input that has no answer.
+    Unsolvable(String),
+    /// Bad command line arguments.
+    Usage(String),
+}
+
+impl Error {
+    /// A short machine readable name for the variant.
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            Error::Io{..} => "io",
+            Error::Parse{..} => "parse",
+            Error::InvalidState(_) => "invalid_state",
+            Error::Unsolvable(_) => "unsolvable",
+            Error::Usage(_) => "usage",
+        }
+    }
+
+    pub fn io<S>(path: S, err: io::Error) -> Self
+        where S: Into<String>
+    {
+        Error::Io{path: path.into(), err}
+    }
+
+    pub fn parse<S>(line: usize, column: usize, msg: S) -> Self
+        where S: Into<String>
+    {
+        Error::Parse{line, column, msg: msg.into()}
+    }
+
+    pub fn invalid<S>(msg: S) -> Self
+        where S: Into<String>
+    {
+        Error::InvalidState(msg.into())
+    }
+
+    pub fn unsolvable<S>(msg: S) -> Self
+        where S: Into<String>
+    {
+        Error::Unsolvable(msg.into())
+    }
+
+    pub fn usage<S>(msg: S) -> Self
+        where S: Into<String>
+    {
+        Error::Usage(msg.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io{ref path, ref err} => write!(f, "{}: {}", path, err),
+            Error::Parse{line, column, ref msg} => write!(f, "{}:{}: {}", line, column, msg),
+            Error::InvalidState(ref msg) => write!(f, "invalid state: {}", msg),
+            Error::Unsolvable(ref msg) => write!(f, "unsolvable: {}", msg),
+            Error::Usage(ref msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io{ref err, ..} => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> From<&'a Error> for Json {
+    fn from(err: &'a Error) -> Self {
+        let mut fields = vec![
+            ("kind", Json::from(err.kind())),
+            ("message", Json::from(err.to_string())),
+        ];
+        if let Error::Parse{line, column, ..} = *err {
+            fields.push(("line", Json::from(line)));
+            fields.push(("column", Json::from(column)));
+        }
+        Json::object(fields)
+    }
+}
+
+/// The 1-based column at which `token` starts, where `token` is a slice of `line`.
+pub fn column(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line.get(..offset).map(|s| s.chars().count()).unwrap_or(0) + 1
+}
+
+/// `line` with a caret under the char at byte offset `at`, for error messages.
+/// Long lines are cut down to a window around `at`.
+pub fn caret(line: &str, at: usize) -> String {
+    const WINDOW: usize = 30;
+    let before: Vec<char> = line[..at].chars().collect();
+    let skip = before.len().saturating_sub(WINDOW);
+    let head: String = before[skip..].iter().collect();
+    let tail: String = line[at..].chars().take(WINDOW + 1).collect();
+    let pre = if skip > 0 { "..." } else { "" };
+    let post = if line[at..].chars().nth(WINDOW + 1).is_some() { "..." } else { "" };
+    format!("  {}{}{}{}\n  {}^", pre, head, tail, post, " ".repeat(pre.len() + before.len() - skip))
+}
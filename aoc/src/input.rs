@@ -0,0 +1,76 @@
+use std::io::{self, Read};
+
+use {Error, Result};
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// Read from a file.
+    File(String),
+    /// Read all of stdin.
+    Stdin,
+    /// The input text itself.
+    Inline(String),
+}
+
+impl Input {
+    /// The conventional input location for a day: `inputs/dayNN.txt`.
+    pub fn default_for(day: u32) -> Self {
+        Input::File(format!("inputs/day{:02}.txt", day))
+    }
+
+    /// Interpret a `--input` argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::File(path.to_owned()),
+        }
+    }
+
+    pub fn load(&self) -> Result<String> {
+        match *self {
+            Input::File(ref path) => read_file(path),
+            Input::Stdin => {
+                let mut contents = String::new();
+                if let Err(err) = std::io::stdin().read_to_string(&mut contents) {
+                    return Err(Error::io("<stdin>", err));
+                }
+                Ok(contents)
+            },
+            Input::Inline(ref s) => Ok(s.clone()),
+        }
+    }
+
+    /// Open for streaming instead of loading everything into memory.
+    pub fn open(&self) -> Result<Box<dyn Read>> {
+        match *self {
+            Input::File(ref path) => match std::fs::File::open(path) {
+                Ok(f) => Ok(Box::new(f)),
+                Err(err) => Err(Error::io(path.as_str(), err)),
+            },
+            Input::Stdin => Ok(Box::new(io::stdin())),
+            Input::Inline(ref s) => Ok(Box::new(io::Cursor::new(s.clone().into_bytes()))),
+        }
+    }
+
+    /// A name for error messages.
+    pub fn name(&self) -> &str {
+        match *self {
+            Input::File(ref path) => path,
+            Input::Stdin => "<stdin>",
+            Input::Inline(_) => "<inline>",
+        }
+    }
+}
+
+pub fn read_file(path: &str) -> Result<String> {
+    let mut file = match std::fs::File::open(path) {
+        Ok(f) => f,
+        Err(err) => return Err(Error::io(path, err)),
+    };
+    let mut contents = String::new();
+    if let Err(err) = file.read_to_string(&mut contents) {
+        return Err(Error::io(path, err));
+    }
+    Ok(contents)
+}
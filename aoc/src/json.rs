@@ -0,0 +1,112 @@
+use std::fmt;
+
+/// Just enough JSON to emit machine readable reports.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Build an object from `(key, value)` pairs, keeping their order.
+    pub fn object<I,K>(fields: I) -> Self
+        where I: IntoIterator<Item=(K, Json)>,
+              K: Into<String>
+    {
+        Json::Object(fields.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+}
+
+impl<'a> From<&'a str> for Json {
+    fn from(s: &'a str) -> Self { Json::Str(s.to_owned()) }
+}
+
+impl From<String> for Json {
+    fn from(s: String) -> Self { Json::Str(s) }
+}
+
+impl From<i64> for Json {
+    fn from(x: i64) -> Self { Json::Int(x) }
+}
+
+impl From<u32> for Json {
+    fn from(x: u32) -> Self { Json::Int(x as i64) }
+}
+
+impl From<usize> for Json {
+    fn from(x: usize) -> Self { Json::Int(x as i64) }
+}
+
+impl From<f64> for Json {
+    fn from(x: f64) -> Self { Json::Float(x) }
+}
+
+impl From<bool> for Json {
+    fn from(x: bool) -> Self { Json::Bool(x) }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(x) => write!(f, "{}", x),
+            Json::Int(x) => write!(f, "{}", x),
+            Json::Float(x) if x.is_finite() => write!(f, "{}", x),
+            Json::Float(_) => write!(f, "null"),
+            Json::Str(ref s) => write_str(f, s),
+            Json::Array(ref xs) => {
+                write!(f, "[")?;
+                for (i, x) in xs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", x)?;
+                }
+                write!(f, "]")
+            },
+            Json::Object(ref fields) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_str(f, k)?;
+                    write!(f, ":{}", v)?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+fn write_str(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+#[test]
+fn display() {
+    let j = Json::object(vec![
+        ("day", Json::from(10u32)),
+        ("answer", Json::from("a\"b\\\n\u{1}")),
+        ("ms", Json::from(1.5)),
+        ("xs", Json::Array(vec![Json::Null, Json::from(true)])),
+    ]);
+    assert_eq!(r#"{"day":10,"answer":"a\"b\\\n\u0001","ms":1.5,"xs":[null,true]}"#, j.to_string());
+}
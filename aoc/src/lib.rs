@@ -0,0 +1,18 @@
+//! Plumbing shared by every day: input loading, errors and day registration.
+
+mod answers;
+mod day;
+mod error;
+mod input;
+mod json;
+pub mod log;
+mod rng;
+mod timing;
+
+pub use answers::{Answers, digest};
+pub use day::{Answer, Day, IntoAnswer, Part, Run, Tool};
+pub use error::{Error, Result, caret, column};
+pub use input::{Input, read_file};
+pub use json::Json;
+pub use rng::Rng;
+pub use timing::{Stats, fmt_duration};
@@ -0,0 +1,51 @@
+/// A small seeded random number generator (SplitMix64) for tests,
+/// benchmarks and generated inputs. Not for real secrets.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`, without modulo bias.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % n;
+            }
+        }
+    }
+
+    /// Shuffle `xs` in place, every order equally likely.
+    pub fn shuffle<T>(&mut self, xs: &mut [T]) {
+        for i in (1..xs.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            xs.swap(i, j);
+        }
+    }
+}
+
+#[test]
+fn seeded() {
+    let mut a = Rng::new(7);
+    let mut b = Rng::new(7);
+    assert_eq!(a.next_u64(), b.next_u64());
+    assert_ne!(a.next_u64(), Rng::new(8).next_u64());
+    let mut counts = [0; 3];
+    for _ in 0..3000 {
+        counts[a.below(3) as usize] += 1;
+    }
+    assert!(counts.iter().all(|&c| c > 900), "{:?}", counts);
+}
@@ -0,0 +1,70 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Summary of repeated timings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarize `samples`, which must not be empty.
+    pub fn of(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "no timing samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        Self{
+            min: sorted[0],
+            median: sorted[sorted.len() / 2],
+            max: sorted[sorted.len() - 1],
+        }
+    }
+
+    /// Time `runs` calls of `f`, which must be at least one.
+    pub fn measure<F>(runs: usize, mut f: F) -> Self
+        where F: FnMut()
+    {
+        let samples: Vec<Duration> = (0..runs).map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        }).collect();
+        Self::of(&samples)
+    }
+}
+
+/// "min / median / max", each formatted with `fmt_duration`.
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} / {} / {}", fmt_duration(self.min), fmt_duration(self.median), fmt_duration(self.max))
+    }
+}
+
+/// Human readable duration with a unit suited to its size.
+pub fn fmt_duration(d: Duration) -> String {
+    let ns = d.as_nanos();
+    if ns < 1_000 {
+        format!("{}ns", ns)
+    } else if ns < 1_000_000 {
+        format!("{:.1}µs", ns as f64 / 1e3)
+    } else if ns < 1_000_000_000 {
+        format!("{:.1}ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.2}s", ns as f64 / 1e9)
+    }
+}
+
+#[test]
+fn stats() {
+    let ms = Duration::from_millis;
+    let s = Stats::of(&[ms(5), ms(1), ms(3), ms(9)]);
+    assert_eq!((ms(1), ms(5), ms(9)), (s.min, s.median, s.max));
+    assert_eq!("1.0ms / 5.0ms / 9.0ms", s.to_string());
+    let mut calls = 0;
+    Stats::measure(3, || calls += 1);
+    assert_eq!(3, calls);
+    assert_eq!("1.5ms", fmt_duration(Duration::from_micros(1500)));
+    assert_eq!("999ns", fmt_duration(Duration::from_nanos(999)));
+}
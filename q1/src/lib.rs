@@ -0,0 +1,150 @@
+extern crate aoc;
+
+mod stream;
+pub use stream::{Digits, captcha_next, captcha_packed};
+use aoc::{Day, Error, Input, Result};
+
+pub fn day() -> Day {
+    Day::new(1)
+        .part(1, parse, q1p1)
+        .part(2, parse, q1p2)
+        .tool("stream", stream_tool)
+}
+
+// aoc stream 1 <path>|- [--part <n>] [--radix <n>]
+// Solves inputs too large to load, such as generated stress tests.
+fn stream_tool(args: &[String]) -> Result<String> {
+    let mut input = None;
+    let mut part = 1;
+    let mut radix = 10;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut number = || {
+            let value = args.next().ok_or_else(|| Error::usage(format!("{} needs a value", arg)))?;
+            value.parse::<u32>().map_err(|_| Error::usage(format!("expected a number: {}", value)))
+        };
+        match arg.as_str() {
+            "--part" => part = number()?,
+            "--radix" => radix = number()?,
+            _ if input.is_none() => input = Some(Input::from_arg(arg)),
+            x => return Err(Error::usage(format!("unexpected argument: {}", x))),
+        }
+    }
+    let input = input.ok_or_else(|| Error::usage("usage: aoc stream 1 <path>|- [--part <n>] [--radix <n>]"))?;
+    let answer = match part {
+        1 => captcha_next(input.open()?, radix, input.name())?,
+        2 => captcha_packed(input.open()?, radix, input.name(), |len| len / 2)?,
+        n => return Err(Error::usage(format!("day 1 has no part {}", n))),
+    };
+    Ok(format!("{}\n", answer))
+}
+
+/// How far ahead of each digit its partner is, given how many digits there are.
+pub trait Offset {
+    fn offset(&self, len: usize) -> usize;
+}
+
+impl Offset for usize {
+    fn offset(&self, _: usize) -> usize { *self }
+}
+
+impl<F> Offset for F
+    where F: Fn(usize) -> usize
+{
+    fn offset(&self, len: usize) -> usize { self(len) }
+}
+
+/// Sum of the digits that match the digit `offset` places ahead,
+/// wrapping around the end.
+pub fn captcha<O>(digits: &[u8], offset: O) -> u64
+    where O: Offset
+{
+    if digits.is_empty() {
+        return 0
+    }
+    let ahead = offset.offset(digits.len()) % digits.len();
+    let partners = digits[ahead..].iter().chain(&digits[..ahead]);
+    digits.iter().zip(partners)
+        .filter(|&(x, y)| x == y)
+        .map(|(&x, _)| x as u64)
+        .sum()
+}
+
+fn parse(s: &str) -> Result<Vec<u8>> {
+    parse_radix(s, 10)
+}
+
+/// Digits in any radix from 2 to 36, ignoring surrounding whitespace.
+pub fn parse_radix(s: &str, radix: u32) -> Result<Vec<u8>> {
+    check_radix(radix)?;
+    let start = s.len() - s.trim_start().len();
+    let mut res = vec!();
+    for (i, c) in s.trim().char_indices() {
+        match c.to_digit(radix) {
+            Some(x) => res.push(x as u8),
+            None => return Err(not_a_digit(s, start + i, c)),
+        }
+    }
+    Ok(res)
+}
+
+// Point at byte `at` of `s` by line, column, byte offset and a snippet.
+fn not_a_digit(s: &str, at: usize, c: char) -> Error {
+    let line_start = s[..at].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end = s[at..].find('\n').map(|i| at + i).unwrap_or(s.len());
+    let line = s[..at].matches('\n').count() + 1;
+    let column = s[line_start..at].chars().count() + 1;
+    Error::parse(line, column, format!("{:?} is not a digit (byte {})\n{}",
+                                       c, at, aoc::caret(&s[line_start..line_end], at - line_start)))
+}
+
+fn check_radix(radix: u32) -> Result<()> {
+    if !(2..=36).contains(&radix) {
+        return Err(Error::invalid(format!("radix {} is not between 2 and 36", radix)));
+    }
+    Ok(())
+}
+
+fn q1p1(digits: Vec<u8>) -> i64 {
+    captcha(&digits, 1) as i64
+}
+
+fn q1p2(digits: Vec<u8>) -> i64 {
+    captcha(&digits, |len| len / 2) as i64
+}
+
+#[test]
+fn radix() {
+    assert_eq!(30, captcha(&parse_radix("fF", 16).unwrap(), 1));
+    assert_eq!(2, captcha(&parse_radix("1101", 2).unwrap(), 1));
+    assert_eq!(35, captcha(&parse_radix("zaz", 36).unwrap(), |len| len - 1));
+    assert!(parse_radix("12", 2).is_err());
+    assert!(parse_radix("12", 37).is_err());
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(vec![1, 2], parse("  12\n").unwrap());
+    assert_eq!("1:4: 'x' is not a digit (byte 3)\n   12x4\n     ^",
+               parse(" 12x4\n").unwrap_err().to_string());
+    assert_eq!("1:3: '\\n' is not a digit (byte 2)\n  12\n    ^",
+               parse("12\n34").unwrap_err().to_string());
+    let long = format!("{}x{}", "1".repeat(40), "2".repeat(40));
+    let msg = parse(&long).unwrap_err().to_string();
+    assert!(msg.ends_with(&format!("\n  ...{}x{}...\n  {}^", "1".repeat(30), "2".repeat(30), " ".repeat(33))), "{}", msg);
+}
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        ("1122", 1, "3"),
+        ("1111", 1, "4"),
+        ("1234", 1, "0"),
+        ("91212129", 1, "9"),
+        ("1212", 2, "6"),
+        ("1221", 2, "0"),
+        ("123425", 2, "4"),
+        ("123123", 2, "12"),
+        ("12131415", 2, "4"),
+    ]);
+}
@@ -0,0 +1,130 @@
+use std::io::{self, BufReader, Read};
+
+use aoc::{Error, Result};
+
+use {Offset, captcha, check_radix};
+
+/// Digits read one byte at a time from a stream. ASCII whitespace is
+/// skipped so that generated inputs may be wrapped over many lines.
+pub struct Digits<R> {
+    bytes: io::Bytes<BufReader<R>>,
+    radix: u32,
+    name: String,
+    line: usize,
+    column: usize,
+}
+
+impl<R> Digits<R>
+    where R: Read
+{
+    /// `name` labels read errors, e.g. the path being read.
+    pub fn new(input: R, radix: u32, name: &str) -> Result<Self> {
+        check_radix(radix)?;
+        Ok(Self{
+            bytes: BufReader::new(input).bytes(),
+            radix,
+            name: name.to_owned(),
+            line: 1,
+            column: 0,
+        })
+    }
+}
+
+impl<R> Iterator for Digits<R>
+    where R: Read
+{
+    type Item = Result<u8>;
+
+    fn next(&mut self) -> Option<Result<u8>> {
+        loop {
+            let b = match self.bytes.next()? {
+                Ok(b) => b,
+                Err(err) => return Some(Err(Error::io(self.name.as_str(), err))),
+            };
+            self.column += 1;
+            if b == b'\n' {
+                self.line += 1;
+                self.column = 0;
+                continue;
+            }
+            if b.is_ascii_whitespace() {
+                continue;
+            }
+            return Some(match (b as char).to_digit(self.radix) {
+                Some(x) if b.is_ascii() => Ok(x as u8),
+                _ => Err(Error::parse(self.line, self.column, format!("byte {:#04x} is not a digit", b))),
+            });
+        }
+    }
+}
+
+/// The next-digit captcha over a stream, in constant memory.
+pub fn captcha_next<R>(input: R, radix: u32, name: &str) -> Result<u64>
+    where R: Read
+{
+    let mut digits = Digits::new(input, radix, name)?;
+    let first = match digits.next() {
+        Some(x) => x?,
+        None => return Ok(0),
+    };
+    let mut prev = first;
+    let mut sum = 0;
+    for x in digits {
+        let x = x?;
+        if x == prev {
+            sum += x as u64;
+        }
+        prev = x;
+    }
+    // The last digit wraps around to the first.
+    if prev == first {
+        sum += first as u64;
+    }
+    Ok(sum)
+}
+
+/// A captcha with any offset over a stream. The offset needs the whole
+/// sequence, so this keeps one byte per digit rather than streaming.
+/// Memory mapping would avoid even that, but needs a crate we don't pull in.
+pub fn captcha_packed<R,O>(input: R, radix: u32, name: &str, offset: O) -> Result<u64>
+    where R: Read,
+          O: Offset
+{
+    let digits = Digits::new(input, radix, name)?.collect::<Result<Vec<u8>>>()?;
+    Ok(captcha(&digits, offset))
+}
+
+// Endless pseudo random decimal digits with a newline every 80.
+#[cfg(test)]
+struct Generated {
+    rng: aoc::Rng,
+    left: usize,
+}
+
+#[cfg(test)]
+impl Read for Generated {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.left);
+        for (i, b) in buf[..n].iter_mut().enumerate() {
+            *b = if (self.left - i).is_multiple_of(81) { b'\n' } else { b'0' + self.rng.below(10) as u8 };
+        }
+        self.left -= n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn streams() {
+    let gen = || Generated{rng: aoc::Rng::new(1), left: 1 << 20};
+    let mut text = String::new();
+    gen().read_to_string(&mut text).unwrap();
+    let digits: Vec<u8> = text.bytes().filter(|b| b.is_ascii_digit()).map(|b| b - b'0').collect();
+    assert_eq!(captcha(&digits, 1), captcha_next(gen(), 10, "gen").unwrap());
+    assert_eq!(captcha(&digits, |len| len / 2), captcha_packed(gen(), 10, "gen", |len| len / 2).unwrap());
+    assert_eq!(0, captcha_next(io::empty(), 10, "empty").unwrap());
+    assert_eq!(7, captcha_next("7\n".as_bytes(), 10, "one").unwrap());
+    match captcha_next("12\n3x".as_bytes(), 10, "bad") {
+        Err(Error::Parse{line: 2, column: 2, ..}) => {},
+        x => panic!("{:?}", x),
+    }
+}
@@ -1,61 +1,47 @@
+#[macro_use]
+extern crate aoc;
+
 use std::borrow::Borrow;
 use std::fmt;
+use aoc::{Day, Error, Result};
 
-type Result<T> = std::result::Result<T, String>;
-
-#[allow(dead_code)]
-fn e<T,S>(msg: S) -> Result<T>
-    where S: Borrow<String>
-{
-    return Err(msg.borrow().to_string());
+pub fn day() -> Day {
+    sized(256)
 }
 
-fn main() {
-    if let Err(err) = main2() {
-        eprintln!("Error: {}", err);
-        std::process::exit(1);
-    }
+// Part 1 on a list of `size` marks; the puzzle's example uses 5.
+fn sized(size: i64) -> Day {
+    Day::new(10)
+        .part(1, parse_p1, move |lengths| q10p1(lengths, size))
+        .part(2, parse_p2, q10p2)
 }
 
-fn main2() -> Result<()> {
-    let intxt = read_file("input.txt")?;
-    let instructions = parse_p2(&intxt)?;
-    let answer = q10p2(instructions)?;
-    println!("{}", answer);
-    Ok(())
+// The first non-blank line and its 1-based line number.
+fn first_line(s: &str) -> Option<(usize, &str)> {
+    s.lines().enumerate()
+        .find(|&(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line))
 }
 
-fn read_file(path: &str) -> Result<String> {
-    use std::io::Read;
-    let mut file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(err) => return Err(format!("open file ({}): {}", path, err)),
-    };
-    let mut contents = String::new();
-    if let Err(err) = file.read_to_string(&mut contents) {
-        return Err(format!("read file: {}", err));
-    }
-    Ok(contents)
-}
-
-#[allow(dead_code)]
 fn parse_p1(s: &str) -> Result<Vec<i64>> {
+    let (n, line) = first_line(s).ok_or_else(|| Error::parse(1, 1, "no input line"))?;
     let mut res = Vec::new();
-    for x in s.trim().lines()
-        .next().ok_or_else(|| "no input line")?
-        .split(',')
-    {
-        res.push(x.trim().parse().map_err(|_| "unexpected non-integer")?);
+    for x in line.split(',') {
+        let x = x.trim();
+        res.push(x.parse().map_err(|_| {
+            Error::parse(n, aoc::column(line, x), format!("unexpected non-integer: {}", x))
+        })?);
     }
     Ok(res)
 }
 
 fn parse_p2(s: &str) -> Result<Vec<u8>> {
     let mut res = Vec::new();
-    if let Some(line) = s.trim().lines().next() {
-        for c in line.trim().chars() {
+    if let Some((n, line)) = first_line(s) {
+        let x = line.trim();
+        for (i, c) in x.chars().enumerate() {
             if !c.is_ascii() {
-                return e(format!("non-ascii char: {}", c))
+                return Err(Error::parse(n, aoc::column(line, x) + i, format!("non-ascii char: {}", c)))
             }
             res.push(c as u8)
         }
@@ -63,30 +49,28 @@ fn parse_p2(s: &str) -> Result<Vec<u8>> {
     Ok(res)
 }
 
-#[allow(dead_code)]
-fn q10p1(lengths: Vec<i64>) -> Result<i64> {
-    println!("{:?}", lengths);
-    let mut s = State::new(256);
-    println!("{:?}", s);
+fn q10p1(lengths: Vec<i64>, size: i64) -> Result<i64> {
+    debug!("{:?}", lengths);
+    let mut s = State::new(size);
+    trace!("{:?}", s);
     for &l in lengths.iter() {
         step(&mut s, l)?;
-        println!("{} -> {:?}", l, s);
+        trace!("{} -> {:?}", l, s);
     }
     Ok(s.list[0] * s.list[1])
 }
 
-#[allow(dead_code)]
 fn q10p2(mut lengths: Vec<u8>) -> Result<String> {
-    println!("{:?}", lengths);
+    debug!("{:?}", lengths);
     let mut suffix = vec![17,31,73,47,23];
     lengths.append(&mut suffix);
     let mut s = State::new(256);
-    println!("{:?}", s);
+    trace!("{:?}", s);
     for round in 0..64 {
-        println!("round: {}", round);
+        trace!("round: {}", round);
         for &l in lengths.iter() {
             step(&mut s, l as i64)?;
-            println!("{} -> {:?}", l, s);
+            trace!("{} -> {:?}", l, s);
             assert_eq!(256, s.list.len());
         }
     }
@@ -114,8 +98,8 @@ fn conv<V>(xs: V) -> Result<Vec<u8>>
 {
     let mut res = Vec::new();
     for x in xs.borrow().iter().cloned() {
-        if x < 0 || x >= 256 {
-            return e(format!("non-u8 value: {}", x))
+        if !(0..256).contains(&x) {
+            return Err(Error::invalid(format!("non-u8 value: {}", x)))
         }
         res.push(x as u8);
     }
@@ -153,6 +137,9 @@ impl fmt::Debug for State {
 }
 
 fn step(s: &mut State, length: i64) -> Result<()> {
+    if length < 0 || length as usize > s.list.len() {
+        return Err(Error::invalid(format!("length {} does not fit list of {}", length, s.list.len())));
+    }
     s.list = reverse_cyclic(&s.list, s.pos as usize, length as usize);
     s.pos += length + s.skip;
     s.pos %= s.list.len() as i64;
@@ -184,9 +171,30 @@ fn p1() {
     assert_eq!(vec![4,3,2,1,0], reverse_cyclic(vec![0,1,2,3,4], 3, 4));
 }
 
+#[test]
+fn p1_small() {
+    let mut s = State::new(5);
+    for &l in [3, 4, 1, 5].iter() {
+        step(&mut s, l).unwrap();
+    }
+    assert_eq!(vec![3, 4, 2, 1, 0], s.list);
+}
+
 #[test]
 fn p2() {
     assert_eq!("1111", hex(vec!(17,17)));
     assert_eq!("0000", hex(vec!(0,0)));
     assert_eq!("a2582a3a0e66e6e86e3812dcb672a272", q10p2(vec![]).unwrap());
 }
+
+#[test]
+fn examples() {
+    // Part 2 always uses 256 marks.
+    sized(5).assert_examples(&[
+        ("3,4,1,5", 1, "12"),
+        ("", 2, "a2582a3a0e66e6e86e3812dcb672a272"),
+        ("AoC 2017", 2, "33efeb34ea91902bb2f59c9920caa6cd"),
+        ("1,2,3", 2, "3efbe78a8d82f29979031a4aa0b16a9d"),
+        ("1,2,4", 2, "63960835bcdc130f0b66d7ff4f6a5a8e"),
+    ]);
+}
@@ -1,43 +1,15 @@
+#[macro_use]
+extern crate aoc;
+
 use std::collections::vec_deque::VecDeque;
-use std::borrow::Borrow;
 use std::collections::HashSet;
 use std::collections::HashMap;
+use aoc::{Day, Error, Result};
 
-type Result<T> = std::result::Result<T, String>;
-
-#[allow(dead_code)]
-fn e<T,S>(msg: S) -> Result<T>
-    where S: Borrow<str>
-{
-    return Err(msg.borrow().to_string());
-}
-
-fn main() {
-    if let Err(err) = main2() {
-        eprintln!("Error: {}", err);
-        std::process::exit(1);
-    }
-}
-
-fn main2() -> Result<()> {
-    let intxt = read_file("input.txt")?;
-    let intermediate = parse(&intxt)?;
-    let answer = q11p2(intermediate)?;
-    println!("{}", answer);
-    Ok(())
-}
-
-fn read_file(path: &str) -> Result<String> {
-    use std::io::Read;
-    let mut file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(err) => return Err(format!("open file ({}): {}", path, err)),
-    };
-    let mut contents = String::new();
-    if let Err(err) = file.read_to_string(&mut contents) {
-        return Err(format!("read file: {}", err));
-    }
-    Ok(contents)
+pub fn day() -> Day {
+    Day::new(11)
+        .part(1, parse, q11p1)
+        .part(2, parse, q11p2)
 }
 
 #[derive(Debug)]
@@ -51,16 +23,16 @@ enum Dir {
 }
 
 impl Dir {
-    fn parse(s: &str) -> Result<Self> {
+    fn parse(s: &str) -> Option<Self> {
         use Dir::*;
         match s {
-            "n" => Ok(N),
-            "nw" => Ok(NW),
-            "ne" => Ok(NE),
-            "sw" => Ok(SW),
-            "se" => Ok(SE),
-            "s" => Ok(S),
-            s => e(format!("unrecognized direction: {}", s))
+            "n" => Some(N),
+            "nw" => Some(NW),
+            "ne" => Some(NE),
+            "sw" => Some(SW),
+            "se" => Some(SE),
+            "s" => Some(S),
+            _ => None,
         }
     }
 
@@ -91,7 +63,7 @@ struct Pos {
 impl Pos {
     fn origin() -> Self { Self{x: 0, y: 0} }
 
-    fn at(x: i64, y: i64) -> Self { Self{x: x, y: y} }
+    fn at(x: i64, y: i64) -> Self { Self{x, y} }
 
     fn neighbors(&self) -> Vec<Self> {
         let s = self;
@@ -119,41 +91,42 @@ impl std::ops::AddAssign for Pos {
 }
 
 fn parse(s: &str) -> Result<Vec<Dir>> {
+    let (i, line) = s.lines().enumerate()
+        .find(|&(_, line)| !line.trim().is_empty())
+        .ok_or_else(|| Error::parse(1, 1, "no input line"))?;
     let mut res = Vec::new();
-    for x in s.trim().lines()
-        .next().ok_or_else(|| "no input line")?
-        .split(',')
-    {
-        res.push(Dir::parse(x.trim())?)
+    for x in line.split(',') {
+        let x = x.trim();
+        res.push(Dir::parse(x).ok_or_else(|| {
+            Error::parse(i + 1, aoc::column(line, x), format!("unrecognized direction: {}", x))
+        })?)
     }
     Ok(res)
 }
 
-#[allow(dead_code)]
 fn q11p1(path: Vec<Dir>) -> Result<i64> {
-    println!("path: {:?}", path);
+    debug!("path: {:?}", path);
     let start = Pos::origin();
     let mut p = start;
     for (i,d) in path.iter().enumerate() {
         p += Dir::unit(d);
-        println!("{} {:?}: {:?}", i, d, p);
+        trace!("{} {:?}: {:?}", i, d, p);
     }
     for y in Bloom::new(start) {
         if p == y.pos {
             return Ok(y.distance as i64)
         }
     }
-    return e("unreachable end of search")
+    Err(Error::invalid("unreachable end of search"))
 }
 
-#[allow(dead_code)]
 fn q11p2(path_dirs: Vec<Dir>) -> Result<i64> {
-    println!("path: {:?}", path_dirs);
+    debug!("path: {:?}", path_dirs);
     let start = Pos::origin();
     let mut path = vec![start];
     for (i,d) in path_dirs.iter().enumerate() {
         let p = *path.last().unwrap() + Dir::unit(d);
-        println!("{} {:?}: {:?}", i, d, p);
+        trace!("{} {:?}: {:?}", i, d, p);
         path.push(p);
     }
 
@@ -161,7 +134,7 @@ fn q11p2(path_dirs: Vec<Dir>) -> Result<i64> {
     let mut path_dists = vec![0];
     let mut distmap: HashMap<Pos, usize> = HashMap::new();
     for (i,p) in path.iter().enumerate() {
-        println!("searching for [{}]: {:?}", i, p);
+        trace!("searching for [{}]: {:?}", i, p);
         'inner: loop {
             if let Some(&dist) = distmap.get(p) {
                 path_dists.push(dist);
@@ -170,7 +143,7 @@ fn q11p2(path_dirs: Vec<Dir>) -> Result<i64> {
             if let Some(item) = search.next() {
                 distmap.insert(item.pos, item.distance);
             } else {
-                return e("unreachable end of search")
+                return Err(Error::invalid("unreachable end of search"))
             }
         }
     }
@@ -215,3 +188,15 @@ impl Iterator for Bloom {
         None // probably should never happen
     }
 }
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        ("ne,ne,ne", 1, "3"),
+        ("ne,ne,sw,sw", 1, "0"),
+        ("ne,ne,s,s", 1, "2"),
+        ("se,sw,se,sw,sw", 1, "3"),
+        ("ne,ne,sw,sw", 2, "2"),
+        ("se,sw,se,sw,sw", 2, "3"),
+    ]);
+}
@@ -1,95 +1,68 @@
-use std::borrow::Borrow;
+#[macro_use]
+extern crate aoc;
+
 use std::collections::{BTreeSet,HashMap};
 use std::hash::Hash;
-
-type Result<T> = std::result::Result<T, String>;
+use aoc::{Day, Error, Result};
 
 type ID = i64;
 
-#[allow(dead_code)]
-fn e<T,S>(msg: S) -> Result<T>
-    where S: Borrow<str>
-{
-    return Err(msg.borrow().to_string());
-}
-
-fn main() {
-    if let Err(err) = main2() {
-        eprintln!("Error: {}", err);
-        std::process::exit(1);
-    }
-}
-
-fn main2() -> Result<()> {
-    let intxt = read_file("input.txt")?;
-    let intermediate = parse(&intxt)?;
-    let answer = q13p1(intermediate)?;
-    println!("{}", answer);
-    Ok(())
-}
-
-fn read_file(path: &str) -> Result<String> {
-    use std::io::Read;
-    let mut file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(err) => return Err(format!("open file ({}): {}", path, err)),
-    };
-    let mut contents = String::new();
-    if let Err(err) = file.read_to_string(&mut contents) {
-        return Err(format!("read file: {}", err));
-    }
-    Ok(contents)
+pub fn day() -> Day {
+    Day::new(13)
+        .part(1, parse, q13p1)
+        .part(2, parse, q13p2)
 }
 
 fn parse(s: &str) -> Result<Vec<(ID, Vec<ID>)>> {
     let mut res = Vec::new();
-    for line in s.trim().lines() {
-        let sides: Vec<&str> = line.trim().split("<->").collect();
+    for (i, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let id = |s: &str| -> Result<ID> {
+            let s = s.trim();
+            s.parse().map_err(|_| Error::parse(i + 1, aoc::column(line, s), format!("malformed ID: {}", s)))
+        };
+        let sides: Vec<&str> = line.split("<->").collect();
         if sides.len() != 2 {
-            return e(format!("line has bad format: {}", line.trim()));
+            return Err(Error::parse(i + 1, 1, format!("line has bad format: {}", line.trim())));
         }
-        let from: ID = sides[0].trim().parse().map_err(|_| "malformed ID")?;
+        let from: ID = id(sides[0])?;
         let mut tos: Vec<ID> = Vec::new();
         for s in sides[1].split(',') {
-            let to: ID = s.trim().parse().map_err(|_| "malformed ID")?;
-            tos.push(to);
-        }
-        if tos.len() == 0 {
-            return e("no IDs on right side of arrow");
+            tos.push(id(s)?);
         }
         res.push((from, tos));
     }
     Ok(res)
 }
 
-#[allow(dead_code)]
 fn q13p1(input: Vec<(ID, Vec<ID>)>) -> Result<i64> {
-    println!("input: {:?}", input);
+    debug!("input: {:?}", input);
     let mut village = Village::new();
     for (from, tos) in input {
         for to in tos {
             village.connect(from, to);
         }
     }
-    println!("pipes: {:?}", village.pipes);
+    trace!("pipes: {:?}", village.pipes);
     let ns = village.transitive_neighbors(0);
-    println!("transitive neighbors of 0: {:?}", ns);
+    debug!("transitive neighbors of 0: {:?}", ns);
     Ok(ns.len() as i64)
 }
 
-#[allow(dead_code)]
 fn q13p2(input: Vec<(ID, Vec<ID>)>) -> Result<i64> {
-    println!("input: {:?}", input);
+    debug!("input: {:?}", input);
     let mut village = Village::new();
     for (from, tos) in input {
         for to in tos {
             village.connect(from, to);
         }
     }
-    println!("pipes: {:?}", village.pipes);
+    trace!("pipes: {:?}", village.pipes);
 
     type Group = BTreeSet<ID>;
-    let mut groups: BTreeSet<Group> = BTreeSet::new();;
+    let mut groups: BTreeSet<Group> = BTreeSet::new();
 
     for id in village.ids() {
         let g: Group = village.transitive_neighbors(id);
@@ -119,7 +92,7 @@ impl Village {
     }
 
     fn neighbors(&self, of: ID) -> BTreeSet<ID> {
-        self.pipes.get(&of).cloned().unwrap_or_else(|| BTreeSet::new())
+        self.pipes.get(&of).cloned().unwrap_or_else(BTreeSet::new)
     }
 
     fn transitive_neighbors(&self, of: ID) -> BTreeSet<ID> {
@@ -152,3 +125,20 @@ fn merge<K,V>(map: &mut HashMap<K, BTreeSet<V>>, key: K, val: V)
     set.insert(val);
     map.insert(key, set);
 }
+
+#[cfg(test)]
+const PIPES: &str = "0 <-> 2
+1 <-> 1
+2 <-> 0, 3, 4
+3 <-> 2, 4
+4 <-> 2, 3, 6
+5 <-> 6
+6 <-> 4, 5";
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        (PIPES, 1, "6"),
+        (PIPES, 2, "2"),
+    ]);
+}
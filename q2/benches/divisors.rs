@@ -0,0 +1,48 @@
+//! Compare the two evenly divisible pair searches on long generated rows.
+//! Run with `cargo bench -p q2`.
+
+extern crate aoc;
+extern crate q2;
+
+use aoc::{Rng, Stats};
+use q2::{EvenQuotient, EvenQuotientByMultiples, RowReducer};
+
+const RUNS: usize = 5;
+
+fn main() {
+    let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+    for &len in [1_000, 10_000, 30_000].iter() {
+        // Clustered cells, then the same with one far larger outlier, then
+        // half of them outliers, which is quadratic for both searches.
+        for &(label, huge) in [("clustered", 0), ("one outlier", 1), ("half outliers", len / 2)].iter() {
+            let row = stress_row(&mut rng, len - huge, huge);
+            let naive = time(&EvenQuotient, &row);
+            let multiples = time(&EvenQuotientByMultiples, &row);
+            println!("{:6} cells, {:13}: pair scan {}, multiples {} (min / median / max of {})",
+                     row.len(), label, naive, multiples, RUNS);
+        }
+    }
+}
+
+fn time<R>(reducer: &R, row: &[i64]) -> Stats
+    where R: RowReducer
+{
+    Stats::measure(RUNS, || assert_eq!(Some(2), reducer.reduce(row)))
+}
+
+// `small` distinct even cells in [n, 1.5n) and `huge` distinct odd cells in
+// [h, 2h), where h is far above `small` * 2n. Neither group divides within
+// itself and no even cell divides an odd one, so doubling the largest small
+// cell plants the only pair, and the scan only reaches it at the end.
+fn stress_row(rng: &mut Rng, small: usize, huge: usize) -> Vec<i64> {
+    let n = 1_000_000;
+    let h = 1 << 60;
+    let mut row: Vec<i64> = (0..small).map(|_| (n + rng.below(n as u64 / 2) as i64) & !1).collect();
+    row.sort_unstable();
+    row.dedup();
+    let planted = 2 * row[row.len() - 1];
+    row.extend((0..huge).map(|_| (h + rng.below(h as u64) as i64) | 1));
+    rng.shuffle(&mut row);
+    row.push(planted);
+    row
+}
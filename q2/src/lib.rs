@@ -0,0 +1,34 @@
+extern crate aoc;
+
+mod reduce;
+mod sheet;
+pub use reduce::{EvenQuotient, EvenQuotientByMultiples, MaxMinusMin, RowError, RowReducer, checksum};
+pub use sheet::{BadCell, Cell, Delimiter, Sheet, SheetError};
+use aoc::{Day, Result};
+
+pub fn day() -> Day {
+    Day::new(2)
+        .part(1, parse, q2p1)
+        .part(2, parse, q2p2)
+}
+
+fn parse(s: &str) -> Result<Sheet> {
+    Ok(Sheet::read(s)?)
+}
+
+fn q2p1(sheet: Sheet) -> Result<i64> {
+    Ok(sheet.checksum(MaxMinusMin)?)
+}
+
+fn q2p2(sheet: Sheet) -> Result<i64> {
+    Ok(sheet.checksum(EvenQuotientByMultiples)?)
+}
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        ("5 1 9 5\n7 5 3\n2 4 6 8", 1, "18"),
+        ("5 9 2 8\n9 4 7 3\n3 8 6 5", 2, "9"),
+        ("5,9,2,8\n9,4,7,3\n3,8,6,5", 2, "9"),
+    ]);
+}
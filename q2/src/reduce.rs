@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+use std::error;
+use std::fmt;
+
+use aoc::Error;
+
+/// Reduces one spreadsheet row to its contribution to the checksum.
+pub trait RowReducer {
+    /// None if the row has no answer under this reducer.
+    fn reduce(&self, row: &[i64]) -> Option<i64>;
+}
+
+/// Largest cell minus smallest cell.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxMinusMin;
+
+impl RowReducer for MaxMinusMin {
+    fn reduce(&self, row: &[i64]) -> Option<i64> {
+        match (row.iter().max(), row.iter().min()) {
+            (Some(max), Some(min)) => max.checked_sub(*min),
+            _ => Some(0),
+        }
+    }
+}
+
+/// The quotient of the one pair of cells where one evenly divides the other.
+#[derive(Debug, Clone, Copy)]
+pub struct EvenQuotient;
+
+impl RowReducer for EvenQuotient {
+    fn reduce(&self, row: &[i64]) -> Option<i64> {
+        if row.is_empty() {
+            return Some(0);
+        }
+        let mut row = row.to_vec();
+        row.sort_unstable();
+        for (i, a) in row.iter().enumerate() {
+            if *a == 0 {
+                continue;
+            }
+            for b in row.split_at(i+1).1.iter() {
+                if b % a == 0 {
+                    return Some(b / a);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Same as `EvenQuotient` but usually much faster on long rows. Every cell
+/// is tested against the largest one directly; for the rest it either looks
+/// up its multiples up to the second largest cell in a hash set or scans the
+/// larger cells, whichever is fewer. That is near linear when the cells are
+/// within a small factor of each other, and a single outlier cannot spoil
+/// it, but many small cells below many much larger ones are still quadratic.
+/// Rows with cells below 1 fall back to `EvenQuotient`. If a row has several
+/// pairs the two may pick different ones.
+#[derive(Debug, Clone, Copy)]
+pub struct EvenQuotientByMultiples;
+
+impl RowReducer for EvenQuotientByMultiples {
+    fn reduce(&self, row: &[i64]) -> Option<i64> {
+        if row.is_empty() || row.iter().any(|&x| x < 1) {
+            return EvenQuotient.reduce(row);
+        }
+        let mut row = row.to_vec();
+        row.sort_unstable();
+        if row.windows(2).any(|w| w[0] == w[1]) {
+            return Some(1);
+        }
+        let (&largest, rest) = row.split_last().unwrap();
+        let limit = *rest.last()?;
+        let cells: HashSet<i64> = rest.iter().cloned().collect();
+        for (i, &a) in rest.iter().enumerate() {
+            if largest % a == 0 {
+                return Some(largest / a);
+            }
+            let larger = &rest[i+1..];
+            if ((limit / a) as usize) < larger.len() {
+                if let Some(k) = (2..=limit / a).find(|k| cells.contains(&(k * a))) {
+                    return Some(k);
+                }
+            } else if let Some(b) = larger.iter().find(|&&b| b % a == 0) {
+                return Some(b / a);
+            }
+        }
+        None
+    }
+}
+
+impl<F> RowReducer for F
+    where F: Fn(&[i64]) -> Option<i64>
+{
+    fn reduce(&self, row: &[i64]) -> Option<i64> {
+        self(row)
+    }
+}
+
+/// A row that a reducer could not handle, or whose value overflowed the
+/// checksum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    /// 1-based.
+    pub row: usize,
+    pub cells: Vec<i64>,
+    pub overflow: bool,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.overflow {
+            return write!(f, "row {} overflows the checksum: {:?}", self.row, self.cells);
+        }
+        write!(f, "row {} cannot be reduced: {:?}", self.row, self.cells)
+    }
+}
+
+impl error::Error for RowError {}
+
+impl From<RowError> for Error {
+    fn from(err: RowError) -> Self {
+        Error::unsolvable(err.to_string())
+    }
+}
+
+/// Sum of every row reduced by `reducer`.
+pub fn checksum<R>(sheet: &[Vec<i64>], reducer: R) -> Result<i64, RowError>
+    where R: RowReducer
+{
+    let mut sum: i64 = 0;
+    for (i, row) in sheet.iter().enumerate() {
+        let err = |overflow| RowError{row: i + 1, cells: row.clone(), overflow};
+        let x = reducer.reduce(row).ok_or_else(|| err(false))?;
+        sum = sum.checked_add(x).ok_or_else(|| err(true))?;
+    }
+    Ok(sum)
+}
+
+#[test]
+fn reducers() {
+    let sheet = vec![vec![5, 9, 2, 8], vec![9, 4, 7, 3], vec![3, 8, 6, 5]];
+    assert_eq!(Ok(18), checksum(&sheet, MaxMinusMin));
+    assert_eq!(Ok(9), checksum(&sheet, EvenQuotient));
+    assert_eq!(Ok(3), checksum(&sheet, |row: &[i64]| Some(row.len() as i64 / 4)));
+    let bad = vec![vec![2, 4], vec![0, 3, 5]];
+    let err = checksum(&bad, EvenQuotient).unwrap_err();
+    assert_eq!(RowError{row: 2, cells: vec![0, 3, 5], overflow: false}, err);
+    assert_eq!("unsolvable: row 2 cannot be reduced: [0, 3, 5]", Error::from(err).to_string());
+    // Extremes overflow a row's difference or the running sum.
+    let wide = vec![vec![i64::MAX, i64::MIN]];
+    assert_eq!(1, checksum(&wide, MaxMinusMin).unwrap_err().row);
+    let tall = vec![vec![0, i64::MAX], vec![0, 1]];
+    let err = checksum(&tall, MaxMinusMin).unwrap_err();
+    assert_eq!(RowError{row: 2, cells: vec![0, 1], overflow: true}, err);
+    assert_eq!("row 2 overflows the checksum: [0, 1]", err.to_string());
+}
+
+#[test]
+fn multiples_match_pair_scan() {
+    let mut rng = aoc::Rng::new(7);
+    let mut next = |n: u64| rng.below(n) as i64;
+    for len in 1..200 {
+        let row: Vec<i64> = (0..len).map(|_| next(1000) + 1).collect();
+        assert_eq!(EvenQuotient.reduce(&row).is_some(), EvenQuotientByMultiples.reduce(&row).is_some(), "{:?}", row);
+        // Odd cells in [n, 2n) never divide each other, so planting
+        // double one of them leaves exactly one pair.
+        let n = 1 + next(10_000);
+        let mut row: Vec<i64> = (0..len).map(|_| (n + next(n as u64)) | 1).filter(|&x| x < 2 * n).collect();
+        row.sort_unstable();
+        row.dedup();
+        let planted = 2 * row[next(row.len() as u64) as usize];
+        row.push(planted);
+        assert_eq!(Some(2), EvenQuotient.reduce(&row));
+        assert_eq!(Some(2), EvenQuotientByMultiples.reduce(&row));
+    }
+    assert_eq!(Some(4), EvenQuotientByMultiples.reduce(&[0, 3, 12]));
+    assert_eq!(Some(1), EvenQuotientByMultiples.reduce(&[7, 5, 7]));
+    assert_eq!(None, EvenQuotientByMultiples.reduce(&[7, 5, 9]));
+    // The largest cell is checked on its own, not by walking up to it.
+    assert_eq!(Some(1 << 40), EvenQuotientByMultiples.reduce(&[3, 5, 5 << 40]));
+    assert_eq!(None, EvenQuotientByMultiples.reduce(&[3, 5, (1 << 61) - 1]));
+}
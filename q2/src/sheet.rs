@@ -0,0 +1,223 @@
+use std::error;
+use std::fmt;
+use std::mem;
+
+use aoc::Error;
+
+use reduce::{RowError, RowReducer, checksum};
+
+/// How the cells on a line are separated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// CSV, where cells may be double quoted.
+    Comma,
+    Tab,
+    /// Runs of whitespace.
+    Space,
+}
+
+impl Delimiter {
+    /// Commas win over tabs, which win over spaces.
+    pub fn detect(s: &str) -> Self {
+        if s.contains(',') {
+            Delimiter::Comma
+        } else if s.contains('\t') {
+            Delimiter::Tab
+        } else {
+            Delimiter::Space
+        }
+    }
+}
+
+/// A number in the sheet with its 1-based row (line) and column (cell).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub row: usize,
+    pub col: usize,
+    pub value: i64,
+}
+
+/// A cell that is not an integer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadCell {
+    pub row: usize,
+    pub col: usize,
+    /// 1-based char offset of the cell within its line.
+    pub column: usize,
+    pub msg: String,
+}
+
+/// Every bad cell in a sheet, in reading order. Never empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetError(pub Vec<BadCell>);
+
+impl fmt::Display for SheetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} bad cell{}", self.0.len(), if self.0.len() == 1 { "" } else { "s" })?;
+        for cell in self.0.iter() {
+            write!(f, "\n  row {} col {}: {}", cell.row, cell.col, cell.msg)?;
+        }
+        Ok(())
+    }
+}
+
+impl error::Error for SheetError {}
+
+impl From<SheetError> for Error {
+    fn from(err: SheetError) -> Self {
+        let (line, column) = err.0.first().map(|c| (c.row, c.column)).unwrap_or((1, 1));
+        Error::parse(line, column, err.to_string())
+    }
+}
+
+/// Rows of integer cells. Rows may be ragged; blank lines and empty
+/// cells are skipped, but still count towards row and column numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sheet {
+    pub delimiter: Delimiter,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Sheet {
+    /// Read with the delimiter guessed by `Delimiter::detect`.
+    pub fn read(s: &str) -> Result<Self, SheetError> {
+        Self::read_with(s, Delimiter::detect(s))
+    }
+
+    pub fn read_with(s: &str, delimiter: Delimiter) -> Result<Self, SheetError> {
+        let mut rows = Vec::new();
+        let mut bad = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let mut cells = Vec::new();
+            for (j, field) in split(line, delimiter).into_iter().enumerate() {
+                let text = field.text.trim();
+                let column = line[..field.start].chars().count() + 1;
+                let (row, col) = (i + 1, j + 1);
+                if let Some(msg) = field.bad {
+                    bad.push(BadCell{row, col, column, msg: msg.to_owned()});
+                } else if text.is_empty() {
+                    continue;
+                } else if let Ok(value) = text.parse() {
+                    cells.push(Cell{row, col, value});
+                } else {
+                    bad.push(BadCell{row, col, column, msg: format!("{:?} is not an integer", text)});
+                }
+            }
+            if !cells.is_empty() {
+                rows.push(cells);
+            }
+        }
+        if !bad.is_empty() {
+            return Err(SheetError(bad));
+        }
+        Ok(Self{delimiter, rows})
+    }
+
+    /// Just the numbers.
+    pub fn values(&self) -> Vec<Vec<i64>> {
+        self.rows.iter().map(|row| row.iter().map(|c| c.value).collect()).collect()
+    }
+
+    /// Like `checksum`, but errors give the row's line number.
+    pub fn checksum<R>(&self, reducer: R) -> Result<i64, RowError>
+        where R: RowReducer
+    {
+        checksum(&self.values(), reducer).map_err(|mut err| {
+            err.row = self.rows[err.row - 1][0].row;
+            err
+        })
+    }
+}
+
+// The text of one cell and the byte offset it starts at.
+struct Field {
+    start: usize,
+    text: String,
+    bad: Option<&'static str>,
+}
+
+fn split(line: &str, delimiter: Delimiter) -> Vec<Field> {
+    let field = |text: &str| Field{
+        start: text.as_ptr() as usize - line.as_ptr() as usize,
+        text: text.to_owned(),
+        bad: None,
+    };
+    match delimiter {
+        Delimiter::Comma => split_csv(line),
+        Delimiter::Tab => line.split('\t').map(field).collect(),
+        Delimiter::Space => line.split_whitespace().map(field).collect(),
+    }
+}
+
+// Split on commas outside double quotes. Inside quotes "" is a literal quote.
+fn split_csv(line: &str) -> Vec<Field> {
+    let mut res = Vec::new();
+    let mut start = 0;
+    let mut text = String::new();
+    let mut bad = None;
+    // Whether the current field opened a quote, and whether it is still open.
+    let mut quoted = false;
+    let mut open = false;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if open {
+            if c != '"' {
+                text.push(c);
+            } else if chars.peek().map(|&(_, c)| c) == Some('"') {
+                chars.next();
+                text.push('"');
+            } else {
+                open = false;
+            }
+        } else if c == ',' {
+            res.push(Field{start, text: mem::take(&mut text), bad: bad.take()});
+            start = i + 1;
+            quoted = false;
+        } else if c == '"' && !quoted && text.trim().is_empty() {
+            quoted = true;
+            open = true;
+            text.clear();
+        } else {
+            if c == '"' {
+                bad = bad.or(Some("stray quote"));
+            } else if quoted && !c.is_whitespace() {
+                bad = bad.or(Some("text after closing quote"));
+            }
+            text.push(c);
+        }
+    }
+    if open {
+        bad = bad.or(Some("unterminated quote"));
+    }
+    res.push(Field{start, text, bad});
+    res
+}
+
+#[test]
+fn delimiters() {
+    let values = |s: &str| Sheet::read(s).unwrap().values();
+    let want = vec![vec![5, 1, 9, 5], vec![7, 5, 3]];
+    assert_eq!(want, values("5 1 9 5\n\n  7 5   3\n"));
+    assert_eq!(want, values("5\t1\t9\t5\n7\t\t5\t3"));
+    assert_eq!(want, values("5,1, 9 ,5\n7,\"5\",\" 3\",,"));
+    assert_eq!(Delimiter::Tab, Delimiter::detect("1\t2 3"));
+    let sheet = Sheet::read("\n4,,8").unwrap();
+    assert_eq!(Delimiter::Comma, sheet.delimiter);
+    assert_eq!(vec![vec![Cell{row: 2, col: 1, value: 4}, Cell{row: 2, col: 3, value: 8}]], sheet.rows);
+}
+
+#[test]
+fn bad_cells() {
+    let err = Sheet::read("1,x,3\n\"4\"5,\"6\n7,\"8\"\"\"").unwrap_err();
+    let found: Vec<(usize, usize, usize, &str)> = err.0.iter()
+        .map(|c| (c.row, c.col, c.column, c.msg.as_str()))
+        .collect();
+    assert_eq!(vec![
+        (1, 2, 3, "\"x\" is not an integer"),
+        (2, 1, 1, "text after closing quote"),
+        (2, 2, 6, "unterminated quote"),
+        (3, 2, 3, "\"8\\\"\" is not an integer"),
+    ], found);
+    assert_eq!("1:3: 4 bad cells\n  row 1 col 2: \"x\" is not an integer",
+               Error::from(err).to_string().lines().take(2).collect::<Vec<_>>().join("\n"));
+}
@@ -0,0 +1,100 @@
+extern crate aoc;
+
+mod render;
+mod spiral;
+mod stress;
+pub use render::{Cell, render};
+pub use spiral::Spiral;
+pub use stress::{Custom, FillRule, Neighbourhood, Product, StressTest, Sum};
+use aoc::{Day, Error, Result};
+
+pub fn day() -> Day {
+    Day::new(3)
+        .part(1, parse, q3p1)
+        .part(2, parse, q3p2)
+        .tool("viz", viz)
+}
+
+// Anything bigger than 100 rings is too wide to read anyway.
+const VIZ_LIMIT: u64 = 201 * 201;
+
+// aoc viz 3 [--rings <n> | --squares <n>] [--cell index|distance|value]
+fn viz(args: &[String]) -> Result<String> {
+    let mut count = 49;
+    let mut cell = Cell::Index;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or_else(|| Error::usage(format!("{} needs a value", arg)))?;
+        let number = || value.parse::<u64>().map_err(|_| Error::usage(format!("expected a number: {}", value)));
+        match arg.as_str() {
+            "--rings" => count = number()?.checked_mul(2)
+                .and_then(|n| n.checked_add(1))
+                .and_then(|n| n.checked_mul(n))
+                .unwrap_or(u64::MAX),
+            "--squares" => count = number()?,
+            "--cell" => cell = match value.as_str() {
+                "index" => Cell::Index,
+                "distance" => Cell::Distance,
+                "value" => Cell::Value,
+                x => return Err(Error::usage(format!("unknown cell: {}", x))),
+            },
+            x => return Err(Error::usage(format!("unexpected argument: {}", x))),
+        }
+    }
+    if count > VIZ_LIMIT {
+        return Err(Error::usage(format!("can draw at most {} squares (100 rings)", VIZ_LIMIT)));
+    }
+    Ok(render(count, cell))
+}
+
+fn parse(s: &str) -> Result<u64> {
+    let (i, line) = s.lines().enumerate()
+        .find(|&(_, line)| !line.trim().is_empty())
+        .ok_or_else(|| Error::parse(1, 1, "missing square number"))?;
+    let x = line.trim();
+    let index: u64 = x.parse().map_err(|_| {
+        Error::parse(i + 1, aoc::column(line, x), format!("unexpected non integer: {}", x))
+    })?;
+    if index == 0 {
+        return Err(Error::parse(i + 1, aoc::column(line, x), "squares are numbered from 1"));
+    }
+    Ok(index)
+}
+
+// Steps carrying data from a square back to square 1
+fn q3p1(index: u64) -> i64 {
+    Spiral::from_index(index).distance() as i64
+}
+
+// First value written by the stress test that is larger than the input
+fn q3p2(limit: u64) -> Result<i64> {
+    StressTest::new(Sum(Neighbourhood::Eight))
+        .map(|(_, _, value)| value)
+        .find(|&value| value > limit)
+        .map(|value| value as i64)
+        .ok_or_else(|| Error::unsolvable(format!("stress test overflowed before passing {}", limit)))
+}
+
+#[test]
+fn viz_limits() {
+    let args = |s: &str| s.split_whitespace().map(|a| a.to_owned()).collect::<Vec<_>>();
+    assert_eq!(render(9, Cell::Index), viz(&args("--rings 1")).unwrap());
+    assert!(viz(&args("--rings 100")).is_ok());
+    assert!(viz(&args("--rings 101")).is_err());
+    assert!(viz(&args("--rings 5000000000")).is_err());
+    assert!(viz(&args("--squares 18446744073709551615")).is_err());
+}
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        ("1", 1, "0"),
+        ("12", 1, "3"),
+        ("23", 1, "2"),
+        ("1024", 1, "31"),
+        ("1", 2, "2"),
+        ("5", 2, "10"),
+        ("59", 2, "122"),
+        ("747", 2, "806"),
+    ]);
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use spiral::Spiral;
+use stress::{Neighbourhood, StressTest, Sum};
+
+/// What to print in each square of a rendered spiral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Index,
+    /// Manhattan distance to square 1.
+    Distance,
+    /// The value written by the puzzle's stress test.
+    Value,
+}
+
+/// Draw the first `count` squares as a grid with y upwards and the
+/// origin in brackets. Every cell is padded to the widest label.
+pub fn render(count: u64, cell: Cell) -> String {
+    let labels: Vec<(Spiral, String)> = match cell {
+        Cell::Index => (1..=count).map(|i| (Spiral::from_index(i), i.to_string())).collect(),
+        Cell::Distance => (1..=count).map(|i| {
+            let s = Spiral::from_index(i);
+            (s, s.distance().to_string())
+        }).collect(),
+        // Cut short if the values overflow.
+        Cell::Value => StressTest::new(Sum(Neighbourhood::Eight))
+            .take(count as usize)
+            .map(|(_, s, v)| (s, v.to_string()))
+            .collect(),
+    };
+    if labels.is_empty() {
+        return String::new();
+    }
+    let width = labels.iter().map(|(_, l)| l.len()).max().unwrap_or(0);
+    let (min_x, max_x) = bounds(labels.iter().map(|(s, _)| s.x));
+    let (min_y, max_y) = bounds(labels.iter().map(|(s, _)| s.y));
+    let grid: HashMap<Spiral, &str> = labels.iter().map(|(s, l)| (*s, l.as_str())).collect();
+    let mut out = String::new();
+    for y in (min_y..=max_y).rev() {
+        let mut line = String::new();
+        for x in min_x..=max_x {
+            let s = Spiral::new(x, y);
+            line.push_str(&match grid.get(&s) {
+                Some(l) if s == Spiral::origin() => format!("[{:>1$}]", l, width),
+                Some(l) => format!(" {:>1$} ", l, width),
+                None => " ".repeat(width + 2),
+            });
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn bounds<I>(xs: I) -> (i64, i64)
+    where I: Iterator<Item=i64>
+{
+    xs.fold((0, 0), |(lo, hi), x| (lo.min(x), hi.max(x)))
+}
+
+#[test]
+fn grids() {
+    assert_eq!(" 5  4  3\n 6 [1] 2\n 7  8  9\n", render(9, Cell::Index));
+    assert_eq!(" 2  1  2\n 1 [0] 1\n 2  1\n", render(8, Cell::Distance));
+    assert_eq!("  5   4   2\n 10 [ 1]  1\n 11  23  25\n", render(9, Cell::Value));
+    assert_eq!("[1]\n", render(1, Cell::Index));
+    assert_eq!("", render(0, Cell::Index));
+}
@@ -0,0 +1,206 @@
+use std::cmp::max;
+
+// How many squares in this layer border
+// layer(0) -> 1
+// layer(1) -> 8
+// layer(2) -> 16
+
+// How long is each side of this layer
+// side(0) -> 1
+// side(1) -> 3
+// side(2) -> 5
+
+// side(n) -> 1 + 2n
+
+// layer(n+1) -> 4 + 4 * side(n)
+
+// layer 0: 0
+// layer 1: 1 2 1 2 1 2 1 2
+// layer 2: 3 2 3 4 3 2 3 4 3 2 3 4 3 2 3 4
+// layer 3: 5 4 3 4 5 6 5 4 3 4 5 6 5 4 3 ...
+
+// mindist(n) -> n
+// maxdist(n) -> 2n
+
+// Squares in layers 0..n: side(n-1)^2 -> (2n - 1)^2
+
+// Length of a size of the nth layer.
+fn side(n: u64) -> u128 {
+    1 + 2 * n as u128
+}
+
+// How many squares in this layer border
+#[cfg(test)]
+fn layer(n: u64) -> u64 {
+    if n == 0 {
+        return 1
+    }
+    (4 + 4 * side(n-1)) as u64
+}
+
+// Minimum distance to the center from layer n.
+#[cfg(test)]
+fn mindist(n: u64) -> u64 { n }
+
+// Maximum distance to the center from layer n.
+#[cfg(test)]
+fn maxdist(n: u64) -> u64 { 2 * n }
+
+/// A square of spiral memory as coordinates relative to square 1.
+/// x grows to the right and y grows upwards, so square 2 is (1, 0)
+/// and square 3 is (1, 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Spiral {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Spiral {
+    pub fn new(x: i64, y: i64) -> Self {
+        Self{x, y}
+    }
+
+    pub fn origin() -> Self {
+        Self::new(0, 0)
+    }
+
+    /// The square numbered `index`. Square 1 is the origin.
+    /// Constant time for every index up to u64::MAX.
+    pub fn from_index(index: u64) -> Self {
+        assert!(index >= 1, "spiral squares are numbered from 1");
+        // Layer n ends at square (2n + 1)^2.
+        let n = (index - 1).isqrt().div_ceil(2);
+        if n == 0 {
+            return Self::origin();
+        }
+        // Each layer starts just above its bottom right corner and runs
+        // anticlockwise in four legs of 2n squares.
+        let consumed = side(n - 1) * side(n - 1);
+        let k = (index as u128 - consumed - 1) as i64;
+        let n = n as i64;
+        let (leg, t) = (k / (2 * n), k % (2 * n));
+        match leg {
+            0 => Self::new(n, 1 - n + t),
+            1 => Self::new(n - 1 - t, n),
+            2 => Self::new(-n, n - 1 - t),
+            _ => Self::new(1 - n + t, -n),
+        }
+    }
+
+    /// The number of this square.
+    /// Panics if it is beyond u64::MAX; see `checked_index`.
+    pub fn index(&self) -> u64 {
+        self.checked_index().unwrap_or_else(|| panic!("{:?} is numbered beyond u64::MAX", self))
+    }
+
+    /// The number of this square, or None if it is beyond u64::MAX.
+    pub fn checked_index(&self) -> Option<u64> {
+        let n = self.layer();
+        if n == 0 {
+            return Some(1);
+        }
+        let consumed = side(n - 1).checked_mul(side(n - 1))?;
+        // Offsets along the legs, in the same order as from_index.
+        let (x, y, n) = (self.x as i128, self.y as i128, n as i128);
+        let k = if x == n && y > -n {
+            y - (1 - n)
+        } else if y == n {
+            2 * n + (n - 1 - x)
+        } else if x == -n {
+            4 * n + (n - 1 - y)
+        } else {
+            6 * n + (x - (1 - n))
+        };
+        let index = consumed.checked_add(k as u128 + 1)?;
+        if index > u64::MAX as u128 {
+            return None;
+        }
+        Some(index as u64)
+    }
+
+    /// Which layer around the origin this square is in.
+    pub fn layer(&self) -> u64 {
+        max(self.x.unsigned_abs(), self.y.unsigned_abs())
+    }
+
+    /// Manhattan distance to square 1.
+    pub fn distance(&self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    /// The eight squares touching this one, diagonals included.
+    pub fn neighbours(&self) -> impl Iterator<Item=Spiral> {
+        let here = *self;
+        (-1..2).flat_map(move |dy| (-1..2).map(move |dx| Spiral::new(here.x + dx, here.y + dy)))
+            .filter(move |&s| s != here)
+    }
+
+    /// The four squares sharing an edge with this one.
+    pub fn adjacent(&self) -> impl Iterator<Item=Spiral> {
+        let here = *self;
+        [(1, 0), (0, 1), (-1, 0), (0, -1)].iter()
+            .map(move |&(dx, dy)| Spiral::new(here.x + dx, here.y + dy))
+    }
+}
+
+// Every square in order, one step at a time: right 1, up 1, left 2, down 2, right 3...
+#[cfg(test)]
+fn walk() -> impl Iterator<Item=Spiral> {
+    let dirs = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+    let steps = (0..).flat_map(move |leg: usize| std::iter::repeat_n(dirs[leg % 4], leg / 2 + 1));
+    std::iter::once(Spiral::origin()).chain(steps.scan(Spiral::origin(), |at, (dx, dy)| {
+        at.x += dx;
+        at.y += dy;
+        Some(*at)
+    }))
+}
+
+// Deterministic pseudo random indices spread over the whole u64 range.
+#[cfg(test)]
+fn sample_indices() -> Vec<u64> {
+    let mut rng = aoc::Rng::new(0x2545_f491_4f6c_dd1d);
+    // Mix in small numbers too, as the high bits are nearly always set.
+    let mut res: Vec<u64> = (0..10_000).map(|i| rng.next_u64() >> (i % 64)).collect();
+    for n in [1u64, 2, 1000, 65_535, 0xffff_ffff >> 1] {
+        let corner = (2 * n + 1) * (2 * n + 1);
+        res.extend_from_slice(&[corner - 1, corner, corner + 1]);
+    }
+    res.extend_from_slice(&[u64::MAX - 1, u64::MAX]);
+    res.retain(|&i| i >= 1);
+    res
+}
+
+#[test]
+fn roundtrip() {
+    assert_eq!(Spiral::new(1, 0), Spiral::from_index(2));
+    assert_eq!(Spiral::new(1, 1), Spiral::from_index(3));
+    assert_eq!(Spiral::new(-1, -1), Spiral::from_index(7));
+    assert_eq!(Spiral::new(1, -1), Spiral::from_index(9));
+    assert_eq!(Spiral::new(2, -1), Spiral::from_index(10));
+    assert_eq!(Spiral::new(2, -2), Spiral::from_index(25));
+    for (index, s) in (1..100_000).zip(walk()) {
+        assert_eq!(s, Spiral::from_index(index), "square {}", index);
+        assert_eq!(index, s.index(), "{:?}", s);
+    }
+}
+
+#[test]
+fn closed_form_properties() {
+    for index in sample_indices() {
+        let s = Spiral::from_index(index);
+        assert_eq!(index, s.index(), "{:?}", s);
+        let n = s.layer();
+        assert!(mindist(n) <= s.distance() && s.distance() <= maxdist(n), "{:?}", s);
+        if n > 0 {
+            // The squares before this layer fill a (2n - 1)-wide block.
+            let first = (side(n - 1) * side(n - 1)) as u64 + 1;
+            assert!(first <= index && index - first < layer(n), "{} in layer {}", index, n);
+        }
+        if index < u64::MAX {
+            let next = Spiral::from_index(index + 1);
+            assert_eq!(1, (next.x - s.x).abs() + (next.y - s.y).abs(), "{} to {}", index, index + 1);
+        }
+    }
+    assert_eq!(None, Spiral::new(1 << 32, 0).checked_index());
+    assert_eq!(None, Spiral::new(i64::MIN, i64::MIN).checked_index());
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use spiral::Spiral;
+
+/// Which squares count as neighbours when filling a square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Squares sharing an edge.
+    Four,
+    /// Squares sharing an edge or a corner.
+    Eight,
+}
+
+impl Neighbourhood {
+    fn of(self, square: Spiral) -> Vec<Spiral> {
+        match self {
+            Neighbourhood::Four => square.adjacent().collect(),
+            Neighbourhood::Eight => square.neighbours().collect(),
+        }
+    }
+}
+
+/// How the stress test computes a square's value from its neighbours.
+pub trait FillRule {
+    fn neighbourhood(&self) -> Neighbourhood {
+        Neighbourhood::Eight
+    }
+
+    /// The value for a square given its already filled neighbours,
+    /// or None to stop the test, e.g. on overflow.
+    fn fill(&self, neighbours: &[u64]) -> Option<u64>;
+}
+
+/// Sum of the filled neighbours. `Sum(Neighbourhood::Eight)` is the puzzle's rule.
+#[derive(Debug, Clone, Copy)]
+pub struct Sum(pub Neighbourhood);
+
+impl FillRule for Sum {
+    fn neighbourhood(&self) -> Neighbourhood { self.0 }
+
+    fn fill(&self, neighbours: &[u64]) -> Option<u64> {
+        neighbours.iter().try_fold(0u64, |acc, &v| acc.checked_add(v))
+    }
+}
+
+/// Product of the filled neighbours.
+#[derive(Debug, Clone, Copy)]
+pub struct Product(pub Neighbourhood);
+
+impl FillRule for Product {
+    fn neighbourhood(&self) -> Neighbourhood { self.0 }
+
+    fn fill(&self, neighbours: &[u64]) -> Option<u64> {
+        neighbours.iter().try_fold(1u64, |acc, &v| acc.checked_mul(v))
+    }
+}
+
+/// A closure over the neighbours it picks.
+#[derive(Debug, Clone, Copy)]
+pub struct Custom<F>(pub Neighbourhood, pub F);
+
+impl<F> FillRule for Custom<F>
+    where F: Fn(&[u64]) -> Option<u64>
+{
+    fn neighbourhood(&self) -> Neighbourhood { self.0 }
+
+    fn fill(&self, neighbours: &[u64]) -> Option<u64> {
+        (self.1)(neighbours)
+    }
+}
+
+/// Any closure over the eight neighbours; see `Custom` for other neighbourhoods.
+impl<F> FillRule for F
+    where F: Fn(&[u64]) -> Option<u64>
+{
+    fn fill(&self, neighbours: &[u64]) -> Option<u64> {
+        self(neighbours)
+    }
+}
+
+/// Lazily fills the spiral from square 1, which holds 1, yielding
+/// `(index, square, value)` until the rule gives up.
+pub struct StressTest<R> {
+    rule: R,
+    values: HashMap<Spiral, u64>,
+    index: u64,
+    done: bool,
+}
+
+impl<R> StressTest<R>
+    where R: FillRule
+{
+    pub fn new(rule: R) -> Self {
+        Self{
+            rule,
+            values: HashMap::new(),
+            index: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R> Iterator for StressTest<R>
+    where R: FillRule
+{
+    type Item = (u64, Spiral, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.index == u64::MAX {
+            return None;
+        }
+        self.index += 1;
+        let square = Spiral::from_index(self.index);
+        let value = if self.index == 1 {
+            1
+        } else {
+            let filled: Vec<u64> = self.rule.neighbourhood().of(square).iter()
+                .filter_map(|s| self.values.get(s).cloned())
+                .collect();
+            match self.rule.fill(&filled) {
+                Some(value) => value,
+                None => {
+                    self.done = true;
+                    return None;
+                },
+            }
+        };
+        self.values.insert(square, value);
+        Some((self.index, square, value))
+    }
+}
+
+#[test]
+fn rules() {
+    let first = |it: &mut dyn Iterator<Item=(u64, Spiral, u64)>| -> Vec<u64> {
+        it.take(10).map(|(_, _, v)| v).collect()
+    };
+    assert_eq!(vec![1, 1, 2, 4, 5, 10, 11, 23, 25, 26], first(&mut StressTest::new(Sum(Neighbourhood::Eight))));
+    assert_eq!(vec![1, 1, 1, 2, 2, 3, 3, 4, 5, 5], first(&mut StressTest::new(Sum(Neighbourhood::Four))));
+    assert_eq!(vec![1; 10], first(&mut StressTest::new(Product(Neighbourhood::Eight))));
+    let counted = |n: &[u64]| Some(n.len() as u64);
+    assert_eq!(vec![1, 1, 2, 3, 2, 3, 2, 4, 3, 2], first(&mut StressTest::new(counted)));
+    assert_eq!(vec![1, 1, 1, 2, 1, 2, 1, 2, 2, 1], first(&mut StressTest::new(Custom(Neighbourhood::Four, counted))));
+    let (index, square, _) = StressTest::new(Sum(Neighbourhood::Eight)).nth(22).unwrap();
+    assert_eq!((23, Spiral::new(0, -2)), (index, square));
+    // Sums overflow u64 long before the spiral runs out.
+    assert!(StressTest::new(Sum(Neighbourhood::Eight)).count() < 1000);
+}
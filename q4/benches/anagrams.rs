@@ -0,0 +1,53 @@
+//! Count anagram-free lines of a million line generated file, comparing
+//! letter-count signatures against sorting every word's chars.
+//! Run with `cargo bench -p q4`.
+
+extern crate aoc;
+extern crate q4;
+
+use std::collections::HashSet;
+
+use aoc::{Rng, Stats};
+use q4::{NoAnagrams, PassphrasePolicy, count_valid};
+
+const LINES: usize = 1_000_000;
+const RUNS: usize = 5;
+
+fn main() {
+    let input = generate(LINES);
+    let sorted_chars = |words: &[&str]| {
+        let mut seen = HashSet::new();
+        words.iter().all(|w| {
+            let mut cs: Vec<char> = w.chars().collect();
+            cs.sort();
+            seen.insert(cs)
+        })
+    };
+    let want = count_valid(&input, &sorted_chars);
+    println!("{} lines, {} MB, {} valid", LINES, input.len() >> 20, want);
+    for &(name, policy) in [("signatures", &NoAnagrams as &dyn PassphrasePolicy),
+                            ("sorted chars", &sorted_chars)].iter() {
+        let s = Stats::measure(RUNS, || assert_eq!(want, count_valid(&input, policy)));
+        println!("{:>12}: {} (min / median / max of {})", name, s, RUNS);
+    }
+}
+
+// Lines of 5 to 10 words of 3 to 7 letters from a small alphabet, so
+// that some lines hold anagrams.
+fn generate(lines: usize) -> String {
+    let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+    let mut below = |n: u64| rng.below(n);
+    let mut res = String::with_capacity(lines * 50);
+    for _ in 0..lines {
+        for i in 0..5 + below(6) {
+            if i > 0 {
+                res.push(' ');
+            }
+            for _ in 0..3 + below(5) {
+                res.push((b'a' + below(8) as u8) as char);
+            }
+        }
+        res.push('\n');
+    }
+    res
+}
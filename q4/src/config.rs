@@ -0,0 +1,158 @@
+use aoc::{Error, Result};
+
+use policy::*;
+
+/// Read a policy file. Every line is a rule expression and all of them
+/// must hold; `#` starts a comment. For example:
+///
+/// ```text
+/// unique
+/// no-anagrams
+/// min-words 3 and max-words 10
+/// not no-palindromes or min-distance 2
+/// ```
+///
+/// The rules are `unique`, `no-anagrams`, `no-palindromes`, `min-words <n>`,
+/// `max-words <n>` and `min-distance <n>`. `not` binds tightest, then `and`,
+/// then `or`, and parentheses group.
+pub fn parse_policy(s: &str) -> Result<Box<dyn PassphrasePolicy>> {
+    let mut res: Option<Box<dyn PassphrasePolicy>> = None;
+    for (i, line) in s.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("");
+        let mut parser = Parser{line, n: i + 1, tokens: tokens(line), pos: 0};
+        if parser.tokens.is_empty() {
+            continue;
+        }
+        let policy = parser.expr()?;
+        if let Some(tok) = parser.peek() {
+            return Err(parser.error(Some(tok), format!("unexpected {:?}", tok)));
+        }
+        res = Some(match res {
+            Some(prev) => Box::new(And(prev, policy)),
+            None => policy,
+        });
+    }
+    Ok(res.unwrap_or_else(|| Box::new(|_: &[&str]| true)))
+}
+
+// Words, with each parenthesis a token of its own.
+fn tokens(line: &str) -> Vec<&str> {
+    let mut res = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            if let Some(s) = start.take() {
+                res.push(&line[s..i]);
+            }
+            if !c.is_whitespace() {
+                res.push(&line[i..i + 1]);
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        res.push(&line[s..]);
+    }
+    res
+}
+
+struct Parser<'a> {
+    line: &'a str,
+    n: usize,
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).cloned()
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let tok = self.peek();
+        self.pos += 1;
+        tok
+    }
+
+    fn eat(&mut self, want: &str) -> bool {
+        if self.peek() == Some(want) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    // Points at `tok`, or just past the end of the line.
+    fn error<S>(&self, tok: Option<&str>, msg: S) -> Error
+        where S: Into<String>
+    {
+        let column = match tok {
+            Some(tok) => aoc::column(self.line, tok),
+            None => self.line.trim_end().chars().count() + 1,
+        };
+        Error::parse(self.n, column, msg)
+    }
+
+    fn expr(&mut self) -> Result<Box<dyn PassphrasePolicy>> {
+        let mut res = self.term()?;
+        while self.eat("or") {
+            res = Box::new(Or(res, self.term()?));
+        }
+        Ok(res)
+    }
+
+    fn term(&mut self) -> Result<Box<dyn PassphrasePolicy>> {
+        let mut res = self.factor()?;
+        while self.eat("and") {
+            res = Box::new(And(res, self.factor()?));
+        }
+        Ok(res)
+    }
+
+    fn factor(&mut self) -> Result<Box<dyn PassphrasePolicy>> {
+        if self.eat("not") {
+            return Ok(Box::new(Not(self.factor()?)));
+        }
+        if self.eat("(") {
+            let res = self.expr()?;
+            let tok = self.next();
+            if tok != Some(")") {
+                return Err(self.error(tok, "expected ')'"));
+            }
+            return Ok(res);
+        }
+        let tok = self.next();
+        Ok(match tok {
+            Some("unique") => Box::new(UniqueWords),
+            Some("no-anagrams") => Box::new(NoAnagrams),
+            Some("no-palindromes") => Box::new(NoPalindromes),
+            Some("min-words") => Box::new(MinWords(self.number()?)),
+            Some("max-words") => Box::new(MaxWords(self.number()?)),
+            Some("min-distance") => Box::new(MinDistance(self.number()?)),
+            Some(x) => return Err(self.error(tok, format!("unknown rule {:?}", x))),
+            None => return Err(self.error(tok, "expected a rule")),
+        })
+    }
+
+    fn number(&mut self) -> Result<usize> {
+        let tok = self.next();
+        tok.and_then(|t| t.parse().ok()).ok_or_else(|| self.error(tok, "expected a number"))
+    }
+}
+
+#[test]
+fn policy_files() {
+    let words = |s: &'static str| s.split_whitespace().collect::<Vec<_>>();
+    let policy = parse_policy("# team rules\nunique\n\nmin-words 2 and not (max-words 1 or no-palindromes) # see #42\n").unwrap();
+    assert!(policy.allows(&words("aa bb")));
+    assert!(!policy.allows(&words("aa aa")));
+    assert!(!policy.allows(&words("ab ba")));
+    assert!(!policy.allows(&words("aa")));
+    assert!(parse_policy("").unwrap().allows(&words("aa aa")));
+    let err = |s| parse_policy(s).err().map(|e| e.to_string());
+    assert_eq!(Some("2:7: unknown rule \"uniq\"".to_owned()), err("unique\nnot   uniq"));
+    assert_eq!(Some("1:10: expected a number".to_owned()), err("min-words"));
+    assert_eq!(Some("1:23: expected ')'".to_owned()), err("(unique or min-words 2"));
+    assert_eq!(Some("1:8: unexpected \"unique\"".to_owned()), err("unique unique"));
+}
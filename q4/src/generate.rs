@@ -0,0 +1,171 @@
+use std::fmt;
+
+use aoc::{Error, Result, Rng};
+
+use policy::PassphrasePolicy;
+
+/// A generated passphrase and the entropy of its generator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Passphrase {
+    pub words: Vec<String>,
+    pub bits: f64,
+}
+
+impl fmt::Display for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.words.join(" "))
+    }
+}
+
+/// Most words a passphrase may have.
+pub const MAX_WORDS: usize = 100;
+
+// Draws per passphrase before deciding the policy can't be met.
+const MAX_TRIES: usize = 10_000;
+
+// Up to this many possible phrases the entropy is counted exactly, beyond
+// it the pass rate is estimated from `CALIBRATION` draws.
+const EXACT_LIMIT: u64 = 100_000;
+const CALIBRATION: usize = 10_000;
+
+/// Draws words uniformly from a wordlist until a phrase passes the policy.
+pub struct Generator<P> {
+    words: Vec<String>,
+    count: usize,
+    policy: P,
+    rng: Rng,
+    bits: f64,
+}
+
+impl<P> Generator<P>
+    where P: PassphrasePolicy
+{
+    /// Duplicate words in `wordlist` are dropped so they don't overstate the
+    /// entropy. Fails if `count` is above `MAX_WORDS`, or if no phrase passes
+    /// the policy, or none did while calibrating.
+    pub fn new<I,S>(wordlist: I, count: usize, policy: P, seed: u64) -> Result<Self>
+        where I: IntoIterator<Item=S>,
+              S: Into<String>
+    {
+        let mut words: Vec<String> = wordlist.into_iter().map(|w| w.into()).collect();
+        words.sort();
+        words.dedup();
+        if words.is_empty() || count == 0 {
+            return Err(Error::invalid("need at least one word to draw and one to pick"));
+        }
+        if count > MAX_WORDS {
+            return Err(Error::invalid(format!("cannot pick {} words, at most {}", count, MAX_WORDS)));
+        }
+        let mut rng = Rng::new(seed);
+        let n = words.len() as u64;
+        let small = (0..count).try_fold(1u64, |acc, _| acc.checked_mul(n).filter(|&x| x <= EXACT_LIMIT));
+        let bits = if small.is_some() {
+            (count_passing(&words, count, &policy) as f64).log2()
+        } else {
+            let mut sample = Rng::new(rng.next_u64());
+            let mut picked = Vec::new();
+            let passed = (0..CALIBRATION)
+                .filter(|_| policy.allows(draw(&mut sample, &words, count, &mut picked)))
+                .count();
+            count as f64 * (n as f64).log2() + (passed as f64 / CALIBRATION as f64).log2()
+        };
+        if bits.is_infinite() {
+            return Err(Error::unsolvable(format!("no phrase of {} words from {} passes {}",
+                                                 count, words.len(), policy.name())));
+        }
+        Ok(Self{words, count, policy, rng, bits})
+    }
+
+    /// Entropy of every phrase this generates: `count` uniform draws less
+    /// what the policy rejects. Exact for small wordlists, otherwise
+    /// estimated once from a sample.
+    pub fn bits(&self) -> f64 {
+        self.bits
+    }
+
+    /// The next passphrase.
+    pub fn generate(&mut self) -> Result<Passphrase> {
+        let mut picked = Vec::new();
+        for _ in 0..MAX_TRIES {
+            if self.policy.allows(draw(&mut self.rng, &self.words, self.count, &mut picked)) {
+                return Ok(Passphrase{
+                    words: picked.iter().map(|&w| w.to_owned()).collect(),
+                    bits: self.bits,
+                });
+            }
+        }
+        Err(Error::unsolvable(format!("no phrase of {} words from {} passed {} after {} tries",
+                                      self.count, self.words.len(), self.policy.name(), MAX_TRIES)))
+    }
+}
+
+// Fill `picked` with `count` uniform draws from `words`.
+fn draw<'a, 'b>(rng: &mut Rng, words: &'a [String], count: usize, picked: &'b mut Vec<&'a str>) -> &'b [&'a str] {
+    picked.clear();
+    for _ in 0..count {
+        picked.push(&words[rng.below(words.len() as u64) as usize]);
+    }
+    picked
+}
+
+// How many of the `words.len()^count` possible draws pass `policy`.
+fn count_passing<P>(words: &[String], count: usize, policy: &P) -> u64
+    where P: PassphrasePolicy
+{
+    let mut idx = vec![0; count];
+    let mut picked = Vec::new();
+    let mut passed = 0;
+    loop {
+        picked.clear();
+        picked.extend(idx.iter().map(|&i| words[i].as_str()));
+        if policy.allows(&picked) {
+            passed += 1;
+        }
+        // Step to the next draw like an odometer.
+        match idx.iter().rposition(|&i| i + 1 < words.len()) {
+            Some(pos) => {
+                idx[pos] += 1;
+                for i in idx[pos+1..].iter_mut() {
+                    *i = 0;
+                }
+            }
+            None => return passed,
+        }
+    }
+}
+
+#[test]
+fn generates() {
+    use policy::{NoAnagrams, UniqueWords};
+    let wordlist = "stop pots tops spot opts post cat act dog god bird fish".split_whitespace();
+    let mut gen = Generator::new(wordlist.clone(), 4, UniqueWords.and(NoAnagrams), 42).unwrap();
+    // Anagram classes of 6, 2, 2, 1 and 1 words: choose 4 classes, a word
+    // from each and an order, 76 * 24 phrases in all.
+    assert!((gen.bits() - 1824f64.log2()).abs() < 1e-9, "{}", gen.bits());
+    let phrases: Vec<Passphrase> = (0..50).map(|_| gen.generate().unwrap()).collect();
+    for p in phrases.iter() {
+        let words: Vec<&str> = p.words.iter().map(|w| w.as_str()).collect();
+        assert!(NoAnagrams.allows(&words), "{}", p);
+        assert_eq!(gen.bits(), p.bits);
+    }
+    let mut again = Generator::new(wordlist, 4, UniqueWords.and(NoAnagrams), 42).unwrap();
+    assert_eq!(phrases[0], again.generate().unwrap());
+    assert!(Generator::new(vec!["ab", "ba"], 2, NoAnagrams, 1).is_err());
+
+    // 200^5 phrases are too many to count, so the pass rate is sampled.
+    let words: Vec<String> = (0..200).map(|i| format!("w{}", i)).collect();
+    let mut gen = Generator::new(words, 5, UniqueWords, 7).unwrap();
+    let exact = (196..=200).map(|n| (n as f64).log2()).sum::<f64>();
+    assert!((gen.bits() - exact).abs() < 0.05, "{} vs {}", gen.bits(), exact);
+    let bits = gen.bits();
+    assert!((0..20).all(|_| gen.generate().unwrap().bits == bits));
+}
+
+#[test]
+fn oversized() {
+    use policy::{MaxWords, UniqueWords};
+    let mut most = Generator::new(vec!["a"], MAX_WORDS, MaxWords(MAX_WORDS), 1).unwrap();
+    assert_eq!(MAX_WORDS, most.generate().unwrap().words.len());
+    let err = Generator::new(vec!["a", "b"], 99_999_999_999, UniqueWords, 1).err().unwrap();
+    assert_eq!("invalid state: cannot pick 99999999999 words, at most 100", err.to_string());
+}
@@ -0,0 +1,149 @@
+extern crate aoc;
+
+mod config;
+mod generate;
+mod phrases;
+mod policy;
+mod report;
+pub use config::parse_policy;
+pub use generate::{Generator, MAX_WORDS, Passphrase};
+pub use phrases::{Phrases, count_valid};
+pub use policy::{And, MaxWords, MinDistance, MinWords, NoAnagrams, NoPalindromes, Not, Or,
+                 PassphrasePolicy, UniqueWords, Violation, levenshtein};
+pub use report::{json_report, text_report, violations};
+use aoc::{Day, Error, Input, Json, Result};
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn day() -> Day {
+    Day::new(4)
+        .part(1, |s| Ok(Phrases::parse(s)), q4p1)
+        .part(2, |s| Ok(Phrases::parse(s)), q4p2)
+        .tool("policy", policy_tool)
+        .tool("report", report_tool)
+        .tool("generate", generate_tool)
+}
+
+// Number of valid passphrases.
+fn q4p1(phrases: Phrases) -> i64 {
+    phrases.count_valid(&UniqueWords) as i64
+}
+
+// Number of valid passphrases under the new draconian policy.
+fn q4p2(phrases: Phrases) -> i64 {
+    phrases.count_valid(&NoAnagrams) as i64
+}
+
+const TOOL_USAGE: &str = "usage: aoc policy|report 4 [<policy file>] [--input <path>|-] [--format text|json]";
+
+// Arguments shared by the policy tools. Without a policy file the part 2
+// rules apply.
+struct ToolArgs {
+    policy: Box<dyn PassphrasePolicy>,
+    input: Input,
+    json: bool,
+}
+
+impl ToolArgs {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut res = ToolArgs{
+            policy: Box::new(UniqueWords.and(NoAnagrams)),
+            input: Input::default_for(4),
+            json: false,
+        };
+        let mut policy = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => res.input = Input::from_arg(args.next().ok_or_else(|| Error::usage(TOOL_USAGE))?),
+                "--format" => res.json = match args.next().map(|s| s.as_str()) {
+                    Some("text") => false,
+                    Some("json") => true,
+                    _ => return Err(Error::usage(TOOL_USAGE)),
+                },
+                _ if policy.is_none() => policy = Some(arg),
+                _ => return Err(Error::usage(TOOL_USAGE)),
+            }
+        }
+        if let Some(path) = policy {
+            res.policy = parse_policy(&aoc::read_file(path)?)?;
+        }
+        Ok(res)
+    }
+}
+
+// aoc policy 4: how many passphrases are valid.
+fn policy_tool(args: &[String]) -> Result<String> {
+    let args = ToolArgs::parse(args)?;
+    let input = args.input.load()?;
+    let valid = count_valid(&input, &args.policy);
+    if args.json {
+        let total = input.lines().filter(|l| !l.trim().is_empty()).count();
+        return Ok(format!("{}\n", Json::object(vec![
+            ("valid", Json::from(valid)),
+            ("invalid", Json::from(total - valid)),
+        ])));
+    }
+    Ok(format!("{}\n", valid))
+}
+
+// aoc report 4: why each rejected passphrase was rejected.
+fn report_tool(args: &[String]) -> Result<String> {
+    let args = ToolArgs::parse(args)?;
+    let found = violations(&args.input.load()?, &args.policy);
+    if args.json {
+        return Ok(json_report(&found));
+    }
+    Ok(text_report(&found))
+}
+
+const GENERATE_USAGE: &str = "usage: aoc generate 4 <wordlist> [--words <n>] [--count <n>] [--seed <n>] [--policy <file>]";
+
+// aoc generate 4: random passphrases that pass a policy, by default the
+// part 2 rules, each with its estimated entropy.
+fn generate_tool(args: &[String]) -> Result<String> {
+    let mut wordlist = None;
+    let mut words = 5;
+    let mut count = 1;
+    let mut seed = None;
+    let mut policy: Box<dyn PassphrasePolicy> = Box::new(UniqueWords.and(NoAnagrams));
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| Error::usage(GENERATE_USAGE));
+        let number = |v: &String| v.parse::<u64>().map_err(|_| Error::usage(format!("expected a number: {}", v)));
+        match arg.as_str() {
+            "--words" => words = number(value()?)? as usize,
+            "--count" => count = number(value()?)?,
+            "--seed" => seed = Some(number(value()?)?),
+            "--policy" => policy = parse_policy(&aoc::read_file(value()?)?)?,
+            _ if wordlist.is_none() => wordlist = Some(arg),
+            _ => return Err(Error::usage(GENERATE_USAGE)),
+        }
+    }
+    let wordlist = aoc::read_file(wordlist.ok_or_else(|| Error::usage(GENERATE_USAGE))?)?;
+    // Unseeded runs differ every time.
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0)
+    });
+    let mut gen = Generator::new(wordlist.split_whitespace(), words, policy, seed)?;
+    let mut out = String::new();
+    for _ in 0..count {
+        let phrase = gen.generate()?;
+        out.push_str(&format!("{}\t{:.1} bits\n", phrase, phrase.bits));
+    }
+    Ok(out)
+}
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        ("aa bb cc dd ee", 1, "1"),
+        ("aa bb cc dd aa", 1, "0"),
+        ("aa bb cc dd aaa", 1, "1"),
+        ("abcde fghij", 2, "1"),
+        ("abcde xyz ecdab", 2, "0"),
+        ("a ab abc abd abf abj", 2, "1"),
+        ("iiii oiii ooii oooi oooo", 2, "1"),
+        ("oiii ioii iioi iiio", 2, "0"),
+    ]);
+}
@@ -0,0 +1,89 @@
+use policy::PassphrasePolicy;
+
+/// The non-blank lines of a passphrase file, with their words copied back
+/// to back into one buffer so the day's parts have a parse step to time.
+/// Checking policies then borrows from that buffer, not from the input;
+/// `count_valid` borrows from the input directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Phrases {
+    // Every word back to back, where each word ends in `text` and where
+    // each phrase's words end in `word_ends`.
+    text: String,
+    word_ends: Vec<usize>,
+    phrase_ends: Vec<usize>,
+}
+
+impl Phrases {
+    pub fn parse(input: &str) -> Self {
+        let mut res = Phrases::default();
+        for line in input.lines() {
+            for word in line.split_whitespace() {
+                res.text.push_str(word);
+                res.word_ends.push(res.text.len());
+            }
+            if res.phrase_ends.last().cloned().unwrap_or(0) < res.word_ends.len() {
+                res.phrase_ends.push(res.word_ends.len());
+            }
+        }
+        res
+    }
+
+    pub fn len(&self) -> usize {
+        self.phrase_ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.phrase_ends.is_empty()
+    }
+
+    /// How many phrases follow `policy`. Words are borrowed from the parsed
+    /// buffer into one reused slice, so this allocates nothing per phrase.
+    pub fn count_valid<P>(&self, policy: &P) -> usize
+        where P: PassphrasePolicy + ?Sized
+    {
+        let mut words = Vec::new();
+        let mut first = 0;
+        self.phrase_ends.iter().filter(|&&end| {
+            words.clear();
+            words.extend((first..end).map(|i| self.word(i)));
+            first = end;
+            policy.allows(&words)
+        }).count()
+    }
+
+    fn word(&self, i: usize) -> &str {
+        let start = if i == 0 { 0 } else { self.word_ends[i - 1] };
+        &self.text[start..self.word_ends[i]]
+    }
+}
+
+/// How many non-blank lines of `input` follow `policy`. Words are borrowed
+/// from `input` into one reused slice, so this allocates nothing per line.
+pub fn count_valid<P>(input: &str, policy: &P) -> usize
+    where P: PassphrasePolicy + ?Sized
+{
+    let mut words = Vec::new();
+    input.lines().filter(|line| {
+        words.clear();
+        words.extend(line.split_whitespace());
+        !words.is_empty() && policy.allows(&words)
+    }).count()
+}
+
+#[test]
+fn phrases() {
+    use policy::UniqueWords;
+    let phrases = Phrases::parse("aa bb\n\n \t\n  cc  aa \nbb bb\n");
+    assert_eq!(3, phrases.len());
+    assert_eq!(vec!["aa", "bb", "cc", "aa", "bb", "bb"], (0..6).map(|i| phrases.word(i)).collect::<Vec<_>>());
+    assert_eq!(2, phrases.count_valid(&UniqueWords));
+    assert!(Phrases::parse("\n  \n").is_empty());
+}
+
+#[test]
+fn borrowed() {
+    use policy::{NoAnagrams, UniqueWords};
+    let input = "aa bb\n\n \t\n  cc  aa \nbb bb\nab ba\n";
+    assert_eq!(Phrases::parse(input).count_valid(&UniqueWords), count_valid(input, &UniqueWords));
+    assert_eq!(2, count_valid(input, &NoAnagrams));
+}
@@ -0,0 +1,378 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Why a passphrase broke a policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The rule that failed, as it would be written in a policy file.
+    pub rule: String,
+    /// The words at fault, if any particular ones are.
+    pub words: Vec<String>,
+    pub reason: String,
+}
+
+impl Violation {
+    fn new<S>(rule: S, words: &[&str], reason: String) -> Self
+        where S: Into<String>
+    {
+        Self{
+            rule: rule.into(),
+            words: words.iter().map(|&w| w.to_owned()).collect(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+/// A rule that every passphrase, given as its words, must follow.
+pub trait PassphrasePolicy {
+    /// Why `words` break the policy, or None if they follow it.
+    fn check(&self, words: &[&str]) -> Option<Violation>;
+
+    /// How the policy is written in a policy file.
+    fn name(&self) -> String;
+
+    fn allows(&self, words: &[&str]) -> bool {
+        self.check(words).is_none()
+    }
+
+    fn and<P>(self, other: P) -> And<Self, P>
+        where Self: Sized
+    {
+        And(self, other)
+    }
+
+    fn or<P>(self, other: P) -> Or<Self, P>
+        where Self: Sized
+    {
+        Or(self, other)
+    }
+
+    fn not(self) -> Not<Self>
+        where Self: Sized
+    {
+        Not(self)
+    }
+}
+
+impl PassphrasePolicy for Box<dyn PassphrasePolicy> {
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        (**self).check(words)
+    }
+
+    fn name(&self) -> String {
+        (**self).name()
+    }
+}
+
+/// Any closure over the words.
+impl<F> PassphrasePolicy for F
+    where F: Fn(&[&str]) -> bool
+{
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        if self(words) {
+            return None;
+        }
+        Some(Violation::new(self.name(), &[], "failed a custom rule".to_owned()))
+    }
+
+    fn name(&self) -> String {
+        "custom".to_owned()
+    }
+}
+
+/// No word appears twice.
+#[derive(Debug, Clone, Copy)]
+pub struct UniqueWords;
+
+impl PassphrasePolicy for UniqueWords {
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        let mut seen = HashSet::new();
+        let w = words.iter().find(|w| !seen.insert(*w))?;
+        Some(Violation::new(self.name(), &[w], format!("'{}' appears twice", w)))
+    }
+
+    fn name(&self) -> String {
+        "unique".to_owned()
+    }
+}
+
+/// No word is an anagram of another.
+#[derive(Debug, Clone, Copy)]
+pub struct NoAnagrams;
+
+impl PassphrasePolicy for NoAnagrams {
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        let (first, w) = first_anagram(words)?;
+        Some(Violation::new(self.name(), &[first, w], format!("'{}' is an anagram of '{}'", w, first)))
+    }
+
+    fn name(&self) -> String {
+        "no-anagrams".to_owned()
+    }
+}
+
+/// Letter counts of a lowercase ASCII word, equal exactly when two words
+/// are anagrams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signature([u8; 26]);
+
+impl Signature {
+    /// None unless `word` is lowercase ASCII and short enough to count in a u8.
+    pub fn of(word: &str) -> Option<Self> {
+        if word.len() > u8::MAX as usize {
+            return None;
+        }
+        let mut counts = [0; 26];
+        for b in word.bytes() {
+            if !b.is_ascii_lowercase() {
+                return None;
+            }
+            counts[(b - b'a') as usize] += 1;
+        }
+        Some(Signature(counts))
+    }
+}
+
+// Phrases up to this long are checked with signatures on the stack.
+const STACK_WORDS: usize = 32;
+
+// The first word that is an anagram of an earlier one, with that earlier word.
+// Allocation free for short lowercase ASCII phrases; anything else falls back
+// to sorting each word's chars.
+fn first_anagram<'a>(words: &[&'a str]) -> Option<(&'a str, &'a str)> {
+    let mut sigs = [Signature([0; 26]); STACK_WORDS];
+    let fast = words.len() <= STACK_WORDS && words.iter().zip(sigs.iter_mut()).all(|(w, sig)| {
+        Signature::of(w).map(|s| *sig = s).is_some()
+    });
+    if fast {
+        for j in 1..words.len() {
+            if let Some(i) = (0..j).find(|&i| sigs[i] == sigs[j]) {
+                return Some((words[i], words[j]));
+            }
+        }
+        return None;
+    }
+    let mut seen = HashMap::new();
+    for &w in words.iter() {
+        let mut cs: Vec<char> = w.chars().collect();
+        cs.sort_unstable();
+        if let Some(&first) = seen.get(&cs) {
+            return Some((first, w));
+        }
+        seen.insert(cs, w);
+    }
+    None
+}
+
+/// At least this many words.
+#[derive(Debug, Clone, Copy)]
+pub struct MinWords(pub usize);
+
+impl PassphrasePolicy for MinWords {
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        if words.len() >= self.0 {
+            return None;
+        }
+        Some(Violation::new(self.name(), &[], format!("{} words is fewer than {}", words.len(), self.0)))
+    }
+
+    fn name(&self) -> String {
+        format!("min-words {}", self.0)
+    }
+}
+
+/// At most this many words.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxWords(pub usize);
+
+impl PassphrasePolicy for MaxWords {
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        if words.len() <= self.0 {
+            return None;
+        }
+        Some(Violation::new(self.name(), &[], format!("{} words is more than {}", words.len(), self.0)))
+    }
+
+    fn name(&self) -> String {
+        format!("max-words {}", self.0)
+    }
+}
+
+/// No word of two or more chars reads the same backwards.
+#[derive(Debug, Clone, Copy)]
+pub struct NoPalindromes;
+
+impl PassphrasePolicy for NoPalindromes {
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        let w = words.iter().find(|w| is_palindrome(w))?;
+        Some(Violation::new(self.name(), &[w], format!("'{}' is a palindrome", w)))
+    }
+
+    fn name(&self) -> String {
+        "no-palindromes".to_owned()
+    }
+}
+
+pub(crate) fn is_palindrome(word: &str) -> bool {
+    word.chars().nth(1).is_some() && word.chars().eq(word.chars().rev())
+}
+
+/// Every pair of words is at least this Levenshtein distance apart.
+#[derive(Debug, Clone, Copy)]
+pub struct MinDistance(pub usize);
+
+impl PassphrasePolicy for MinDistance {
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        for (i, a) in words.iter().enumerate() {
+            for b in words[i+1..].iter() {
+                let d = levenshtein(a, b);
+                if d < self.0 {
+                    return Some(Violation::new(self.name(), &[a, b],
+                                               format!("'{}' is {} edits from '{}', fewer than {}", b, d, a, self.0)));
+                }
+            }
+        }
+        None
+    }
+
+    fn name(&self) -> String {
+        format!("min-distance {}", self.0)
+    }
+}
+
+/// Edits (insertions, deletions or substitutions of chars) to turn `a` into `b`.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitute = prev[j] + if ca == cb { 0 } else { 1 };
+            cur[j + 1] = substitute.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Both policies hold.
+#[derive(Debug, Clone, Copy)]
+pub struct And<A, B>(pub A, pub B);
+
+impl<A, B> PassphrasePolicy for And<A, B>
+    where A: PassphrasePolicy,
+          B: PassphrasePolicy
+{
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        self.0.check(words).or_else(|| self.1.check(words))
+    }
+
+    fn name(&self) -> String {
+        format!("{} and {}", group(self.0.name()), group(self.1.name()))
+    }
+}
+
+/// Either policy holds.
+#[derive(Debug, Clone, Copy)]
+pub struct Or<A, B>(pub A, pub B);
+
+impl<A, B> PassphrasePolicy for Or<A, B>
+    where A: PassphrasePolicy,
+          B: PassphrasePolicy
+{
+    // Both sides failed, so report both.
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        let a = self.0.check(words)?;
+        let b = self.1.check(words)?;
+        let mut res = Violation::new(self.name(), &[], format!("{} and {}", a.reason, b.reason));
+        let extra: Vec<String> = b.words.into_iter().filter(|w| !a.words.contains(w)).collect();
+        res.words = a.words;
+        res.words.extend(extra);
+        Some(res)
+    }
+
+    fn name(&self) -> String {
+        format!("{} or {}", self.0.name(), self.1.name())
+    }
+}
+
+/// The policy does not hold.
+#[derive(Debug, Clone, Copy)]
+pub struct Not<A>(pub A);
+
+impl<A> PassphrasePolicy for Not<A>
+    where A: PassphrasePolicy
+{
+    fn check(&self, words: &[&str]) -> Option<Violation> {
+        if self.0.check(words).is_some() {
+            return None;
+        }
+        Some(Violation::new(self.name(), &[], format!("follows {}", self.0.name())))
+    }
+
+    fn name(&self) -> String {
+        let inner = self.0.name();
+        if inner.contains(" and ") {
+            return format!("not ({})", inner);
+        }
+        format!("not {}", group(inner))
+    }
+}
+
+// Parenthesise `or` so that it reads right next to the tighter operators.
+fn group(name: String) -> String {
+    if name.contains(" or ") {
+        return format!("({})", name);
+    }
+    name
+}
+
+#[test]
+fn built_ins() {
+    let words = |s: &'static str| s.split_whitespace().collect::<Vec<_>>();
+    assert!(UniqueWords.allows(&words("aa bb aaa")));
+    assert!(!UniqueWords.allows(&words("aa bb aa")));
+    assert!(!NoAnagrams.allows(&words("abcde xyz ecdab")));
+    assert!(!NoAnagrams.allows(&words("été éét")) && NoAnagrams.allows(&words("été ete")));
+    let long = "ab ".repeat(STACK_WORDS) + "cd ba";
+    assert_eq!(Some(("ab", "ab")), first_anagram(&long.split_whitespace().collect::<Vec<_>>()));
+    assert_eq!(Some(("ab", "ba")), first_anagram(&["cd", "ab", "ba", "ab"]));
+    assert!(MinWords(2).allows(&words("a b")) && !MinWords(3).allows(&words("a b")));
+    assert!(MaxWords(2).allows(&words("a b")) && !MaxWords(1).allows(&words("a b")));
+    assert!(NoPalindromes.allows(&words("a ab")) && !NoPalindromes.allows(&words("ab aba")));
+    assert_eq!(3, levenshtein("kitten", "sitting"));
+    assert_eq!(4, levenshtein("", "four"));
+    assert!(MinDistance(2).allows(&words("cat dog")) && !MinDistance(2).allows(&words("cat cot")));
+    let short = |w: &[&str]| w.iter().all(|w| w.len() <= 3);
+    assert!(NoPalindromes.not().allows(&words("ab aba")));
+    let policy = UniqueWords.and(NoPalindromes.or(short));
+    assert!(policy.allows(&words("ab aba")));
+    assert!(!policy.allows(&words("ab abba")));
+    assert!(!policy.allows(&words("ab ab")));
+    assert_eq!("unique and (no-palindromes or custom)", policy.name());
+    assert_eq!("not (unique or no-anagrams)", UniqueWords.or(NoAnagrams).not().name());
+}
+
+#[test]
+fn violations() {
+    let words = |s: &'static str| s.split_whitespace().collect::<Vec<_>>();
+    let reason = |p: &dyn PassphrasePolicy, s| p.check(&words(s)).map(|v| v.reason);
+    assert_eq!(Some("'ecdab' is an anagram of 'abcde'".to_owned()), reason(&NoAnagrams, "abcde xyz ecdab"));
+    assert_eq!(Some("'aa' appears twice".to_owned()), reason(&UniqueWords, "aa bb aa"));
+    assert_eq!(Some("'cot' is 1 edits from 'cat', fewer than 2".to_owned()), reason(&MinDistance(2), "cat cot"));
+    assert_eq!(Some("follows max-words 3".to_owned()), reason(&MaxWords(3).not(), "a b"));
+    let either = NoPalindromes.or(MinWords(3));
+    let v = either.check(&words("aba b")).unwrap();
+    assert_eq!("no-palindromes or min-words 3", v.rule);
+    assert_eq!(vec!["aba".to_owned()], v.words);
+    assert_eq!("'aba' is a palindrome and 2 words is fewer than 3", v.reason);
+    assert_eq!(None, either.check(&words("aba b c")));
+    assert_eq!("not (unique and no-anagrams)", UniqueWords.and(NoAnagrams).not().name());
+}
@@ -0,0 +1,46 @@
+use aoc::Json;
+
+use policy::{PassphrasePolicy, Violation};
+
+/// Every non-blank line of `input` that breaks `policy`, with 1-based line numbers.
+pub fn violations<P>(input: &str, policy: &P) -> Vec<(usize, Violation)>
+    where P: PassphrasePolicy + ?Sized
+{
+    input.lines().enumerate().filter_map(|(i, line)| {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() {
+            return None;
+        }
+        policy.check(&words).map(|v| (i + 1, v))
+    }).collect()
+}
+
+/// One line per violation, like "line 17: 'ecdab' is an anagram of 'abcde' (no-anagrams)".
+pub fn text_report(violations: &[(usize, Violation)]) -> String {
+    violations.iter()
+        .map(|&(line, ref v)| format!("line {}: {} ({})\n", line, v.reason, v.rule))
+        .collect()
+}
+
+/// One JSON object per violation and line.
+pub fn json_report(violations: &[(usize, Violation)]) -> String {
+    violations.iter().map(|&(line, ref v)| {
+        format!("{}\n", Json::object(vec![
+            ("line", Json::from(line)),
+            ("rule", Json::from(v.rule.as_str())),
+            ("words", Json::Array(v.words.iter().map(|w| Json::from(w.as_str())).collect())),
+            ("reason", Json::from(v.reason.as_str())),
+        ]))
+    }).collect()
+}
+
+#[test]
+fn reports() {
+    use policy::{NoAnagrams, UniqueWords};
+    let input = "abcde fghij\n\nabcde xyz ecdab\naa bb aa\n";
+    let found = violations(input, &UniqueWords.and(NoAnagrams));
+    assert_eq!("line 3: 'ecdab' is an anagram of 'abcde' (no-anagrams)\nline 4: 'aa' appears twice (unique)\n",
+               text_report(&found));
+    assert_eq!(r#"{"line":3,"rule":"no-anagrams","words":["abcde","ecdab"],"reason":"'ecdab' is an anagram of 'abcde'"}"#,
+               json_report(&found).lines().next().unwrap());
+}
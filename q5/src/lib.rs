@@ -0,0 +1,53 @@
+extern crate aoc;
+
+mod maze;
+pub use maze::{Constant, Escape, Exit, Increment, JumpMaze, JumpRule, Stranger, Threshold};
+use aoc::{Day, Error, Result};
+
+pub fn day() -> Day {
+    Day::new(5)
+        .part(1, parse, q5p1)
+        .part(2, parse, q5p2)
+}
+
+fn parse(s: &str) -> Result<Vec<i64>> {
+    let lines = s.lines().enumerate().map(|(i, l)| {
+        (i, l, l.trim())
+    }).filter(|&(_, _, x)| {
+        !x.is_empty()
+    });
+    let mut res = Vec::new();
+    for (i, line, x) in lines {
+        let n: i64 = x.parse().map_err(|_| {
+            Error::parse(i + 1, aoc::column(line, x), format!("unexpected non integer: {}", x))
+        })?;
+        res.push(n);
+    }
+    Ok(res)
+}
+
+// Number of steps to escape the maze.
+fn q5p1(tape: Vec<i64>) -> Result<i64> {
+    steps(JumpMaze::new(tape).run(Increment))
+}
+
+// Number of steps to escape the maze when large jumps shrink.
+fn q5p2(tape: Vec<i64>) -> Result<i64> {
+    steps(JumpMaze::new(tape).run(Stranger))
+}
+
+fn steps(escape: Escape) -> Result<i64> {
+    if escape.exit == Exit::Overflow {
+        return Err(Error::invalid(format!("an offset overflowed after {} steps", escape.steps)));
+    }
+    Ok(escape.steps as i64)
+}
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        ("0\n3\n0\n1\n-3", 1, "5"),
+        ("0\n3\n0\n1\n-3", 2, "10"),
+    ]);
+    assert!(q5p1(vec![i64::MAX]).is_err());
+}
@@ -0,0 +1,139 @@
+/// How an offset changes after the program jumps by it.
+pub trait JumpRule {
+    /// What to add to `offset` once it has been used.
+    fn change(&self, offset: i64) -> i64;
+}
+
+/// Part 1: every offset goes up by one.
+#[derive(Debug, Clone, Copy)]
+pub struct Increment;
+
+impl JumpRule for Increment {
+    fn change(&self, _: i64) -> i64 { 1 }
+}
+
+/// Part 2: offsets of three or more go down by one, the rest up by one.
+#[derive(Debug, Clone, Copy)]
+pub struct Stranger;
+
+impl JumpRule for Stranger {
+    fn change(&self, offset: i64) -> i64 {
+        Threshold{at: 3, below: 1, at_or_above: -1}.change(offset)
+    }
+}
+
+/// Every offset changes by the same amount.
+#[derive(Debug, Clone, Copy)]
+pub struct Constant(pub i64);
+
+impl JumpRule for Constant {
+    fn change(&self, _: i64) -> i64 { self.0 }
+}
+
+/// Offsets below `at` change by `below`, the rest by `at_or_above`.
+#[derive(Debug, Clone, Copy)]
+pub struct Threshold {
+    pub at: i64,
+    pub below: i64,
+    pub at_or_above: i64,
+}
+
+impl JumpRule for Threshold {
+    fn change(&self, offset: i64) -> i64 {
+        if offset >= self.at {
+            self.at_or_above
+        } else {
+            self.below
+        }
+    }
+}
+
+impl<F> JumpRule for F
+    where F: Fn(i64) -> i64
+{
+    fn change(&self, offset: i64) -> i64 {
+        self(offset)
+    }
+}
+
+/// Which end of the tape the program jumped off, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Start,
+    End,
+    /// Stopped on the tape because the rule took an offset past the range
+    /// of i64. That jump is not taken.
+    Overflow,
+}
+
+/// How a run of the maze went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Escape {
+    pub steps: u64,
+    pub exit: Exit,
+    /// The offsets as they were left.
+    pub tape: Vec<i64>,
+}
+
+/// A tape of jump offsets, starting at the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpMaze {
+    tape: Vec<i64>,
+}
+
+impl JumpMaze {
+    pub fn new(tape: Vec<i64>) -> Self {
+        Self{tape}
+    }
+
+    /// Jump until off the tape. Some rules never get there; see `run_for`.
+    pub fn run<R>(&self, rule: R) -> Escape
+        where R: JumpRule
+    {
+        self.run_for(rule, u64::MAX).expect("maze did not escape")
+    }
+
+    /// Like `run`, but None if still on the tape after `max_steps`.
+    pub fn run_for<R>(&self, rule: R, max_steps: u64) -> Option<Escape>
+        where R: JumpRule
+    {
+        let mut tape = self.tape.clone();
+        let mut pc: i64 = 0;
+        let mut steps = 0;
+        while pc >= 0 && pc < tape.len() as i64 {
+            if steps == max_steps {
+                return None;
+            }
+            let jump = tape[pc as usize];
+            tape[pc as usize] = match jump.checked_add(rule.change(jump)) {
+                Some(offset) => offset,
+                None => return Some(Escape{steps, exit: Exit::Overflow, tape}),
+            };
+            steps += 1;
+            pc = match pc.checked_add(jump) {
+                Some(pc) => pc,
+                // Past the range of i64 is off the tape too.
+                None => return Some(Escape{steps, exit: Exit::End, tape}),
+            };
+        }
+        let exit = if pc < 0 { Exit::Start } else { Exit::End };
+        Some(Escape{steps, exit, tape})
+    }
+}
+
+#[test]
+fn rules() {
+    let maze = JumpMaze::new(vec![0, 3, 0, 1, -3]);
+    assert_eq!(Escape{steps: 5, exit: Exit::End, tape: vec![2, 5, 0, 1, -2]}, maze.run(Increment));
+    assert_eq!(Escape{steps: 10, exit: Exit::End, tape: vec![2, 3, 2, 3, -1]}, maze.run(Stranger));
+    assert_eq!(maze.run(Increment), maze.run(Constant(1)));
+    assert_eq!(maze.run(Stranger), maze.run(|x| if x >= 3 { -1 } else { 1 }));
+    assert_eq!(Some(Exit::Start), maze.run_for(Constant(-1), 100).map(|e| e.exit));
+    assert_eq!(None, maze.run_for(Constant(0), 100));
+    assert_eq!(Exit::Start, JumpMaze::new(vec![-1]).run(Increment).exit);
+    assert_eq!(0, JumpMaze::new(vec![]).run(Increment).steps);
+    let huge = JumpMaze::new(vec![1, i64::MAX]);
+    assert_eq!(Escape{steps: 1, exit: Exit::Overflow, tape: vec![2, i64::MAX]}, huge.run(Increment));
+    assert_eq!(Escape{steps: 2, exit: Exit::End, tape: vec![0, i64::MAX - 1]}, huge.run(Constant(-1)));
+    assert_eq!(Exit::Overflow, JumpMaze::new(vec![i64::MIN]).run(Constant(-1)).exit);
+}
@@ -1,48 +1,31 @@
+#[macro_use]
+extern crate aoc;
+
 mod wrap;
 use wrap::Wrap;
+use aoc::{Day, Error, Result};
 
 use std::collections::HashSet;
 
-type Result<T> = std::result::Result<T, String>;
-
 type Bank = i64;
 type Area = Vec<Bank>;
 
-fn main() {
-    if let Err(err) = main2() {
-        eprintln!("Error: {}", err);
-        std::process::exit(1);
-    }
-}
-
-fn main2() -> Result<()> {
-    let intxt = read_file("input.txt")?;
-    let area = parse(&intxt)?;
-    let answer = q6p2(area);
-    println!("{}", answer);
-    Ok(())
-}
-
-fn read_file(path: &str) -> Result<String> {
-    use std::io::Read;
-    let mut file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(err) => return Err(format!("open file ({}): {}", path, err)),
-    };
-    let mut contents = String::new();
-    if let Err(err) = file.read_to_string(&mut contents) {
-        return Err(format!("read file: {}", err));
-    }
-    Ok(contents)
+pub fn day() -> Day {
+    Day::new(6)
+        .part(1, parse, q6p1)
+        .part(2, parse, q6p2)
 }
 
 fn parse(s: &str) -> Result<Area> {
-    s.trim().lines()
-        .next().ok_or_else(|| format!("unexpected lack of input lines"))
-        .and_then(|line| {
+    s.lines().enumerate()
+        .find(|&(_, line)| !line.trim().is_empty())
+        .ok_or_else(|| Error::parse(1, 1, "unexpected lack of input lines"))
+        .and_then(|(i, line)| {
             let mut res = Vec::new();
-            for x in line.trim().split_whitespace() {
-                let n: i64 = x.parse().map_err(|_| format!("unexpected non integer: {}", x))?;
+            for x in line.split_whitespace() {
+                let n: i64 = x.parse().map_err(|_| {
+                    Error::parse(i + 1, aoc::column(line, x), format!("unexpected non integer: {}", x))
+                })?;
                 res.push(n);
             }
             Ok(res)
@@ -50,12 +33,11 @@ fn parse(s: &str) -> Result<Area> {
 }
 
 // Number of rounds before a dup
-#[allow(dead_code)]
 fn q6p1(a: Area) -> i64 {
     let mut a = Wrap::new(a);
     let mut seen = HashSet::new();
     for round in 0.. {
-        println!("{:?}", a.as_ref());
+        trace!("{:?}", a.as_ref());
         if seen.replace(a.as_ref().clone()).is_some() {
             return round
         }
@@ -69,17 +51,17 @@ fn q6p2(a: Area) -> i64 {
     let mut a = Wrap::new(a);
     let mut seen = HashSet::new();
     for _ in 0.. {
-        println!("{:?}", a.as_ref());
+        trace!("{:?}", a.as_ref());
         if seen.replace(a.as_ref().clone()).is_some() {
             break;
         }
         a.mutate(balancer_round);
     };
     let seeking = a.as_ref().clone();
-    println!("found {:?}", seeking);
+    debug!("found {:?}", seeking);
     // Look again
     for round in 1.. {
-        println!("{:?}", a.as_ref());
+        trace!("{:?}", a.as_ref());
         a.mutate(balancer_round);
         if *a.as_ref() == seeking {
             return round
@@ -114,3 +96,11 @@ fn select_bank(a: &Area) -> usize {
     }
     best_i
 }
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        ("0 2 7 0", 1, "5"),
+        ("0 2 7 0", 2, "4"),
+    ]);
+}
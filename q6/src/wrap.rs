@@ -1,5 +1,3 @@
-use std;
-
 const WRAP_INVARIANT_MSG: &str = "wrap invariant violated: holds no value";
 
 #[derive(Debug)]
@@ -19,12 +17,12 @@ impl<T> Wrap<T> {
     pub fn mutate<F>(&mut self, f: F)
         where F: FnOnce(T) -> T
     {
-        let prev = std::mem::replace(&mut self.inner, None).expect(WRAP_INVARIANT_MSG);
+        let prev = self.inner.take().expect(WRAP_INVARIANT_MSG);
         self.inner = Some(f(prev));
     }
 
     #[allow(dead_code)]
-    pub fn to_inner(self) -> T {
+    pub fn into_inner(self) -> T {
         self.inner.expect(WRAP_INVARIANT_MSG)
     }
 }
@@ -34,7 +32,7 @@ impl<T> AsRef<T> for Wrap<T> {
         match self.inner {
             Some(ref x) => x,
             None => {
-                panic!(WRAP_INVARIANT_MSG)
+                panic!("{}", WRAP_INVARIANT_MSG)
             }
         }
     }
@@ -46,7 +44,7 @@ impl<T> AsMut<T> for Wrap<T> {
         match self.inner {
             Some(ref mut x) => x,
             None => {
-                panic!(WRAP_INVARIANT_MSG)
+                panic!("{}", WRAP_INVARIANT_MSG)
             }
         }
     }
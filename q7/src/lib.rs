@@ -1,54 +1,35 @@
+#[macro_use]
+extern crate aoc;
+
 use std::collections::{HashMap,HashSet};
 use std::hash::Hash;
+use aoc::{Day, Error, Result};
 
 type Name = String;
 type Shouts = Vec<(Name, i64, Vec<Name>)>;
 
-type Result<T> = std::result::Result<T, String>;
-
-fn e<T>(msg: &str) -> Result<T> {
-    return Err(msg.to_owned());
-}
-
-fn main() {
-    if let Err(err) = main2() {
-        eprintln!("Error: {}", err);
-        std::process::exit(1);
-    }
-}
-
-fn main2() -> Result<()> {
-    let intxt = read_file("input.txt")?;
-    let shouts = parse(&intxt)?;
-    let answer = q7p2(shouts)?;
-    println!("{}", answer);
-    Ok(())
-}
-
-fn read_file(path: &str) -> Result<String> {
-    use std::io::Read;
-    let mut file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(err) => return Err(format!("open file ({}): {}", path, err)),
-    };
-    let mut contents = String::new();
-    if let Err(err) = file.read_to_string(&mut contents) {
-        return Err(format!("read file: {}", err));
-    }
-    Ok(contents)
+pub fn day() -> Day {
+    Day::new(7)
+        .part(1, parse, q7p1)
+        .part(2, parse, q7p2)
 }
 
 fn parse(s: &str) -> Result<Shouts> {
     let mut res: Shouts = Vec::new();
-    for line in s.trim().lines() {
-        let mut words = line.trim().split_whitespace();
-        let name = words.next().ok_or_else(|| format!("missing name"))?.to_owned();
+    for (i, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let end = line.chars().count() + 1;
+        let mut words = line.split_whitespace();
+        let name = words.next().ok_or_else(|| Error::parse(i + 1, end, "missing name"))?.to_owned();
         let weight = words.next()
-            .ok_or_else(|| format!("missing weight"))?
+            .ok_or_else(|| Error::parse(i + 1, end, "missing weight"))?
             .trim_matches('(')
             .trim_matches(')');
-        let weight: i64 = weight.parse()
-            .map_err(|_| format!("unexpected non integer: {}", weight))?;
+        let weight: i64 = weight.parse().map_err(|_| {
+            Error::parse(i + 1, aoc::column(line, weight), format!("unexpected non integer: {}", weight))
+        })?;
         let _arrow = words.next();
         let supporting: Vec<Name> = words
             .map(|x2| x2.trim_matches(',').to_owned()).collect();
@@ -58,8 +39,7 @@ fn parse(s: &str) -> Result<Shouts> {
 }
 
 // Number of rounds before a dup
-#[allow(dead_code)]
-fn q7p1(shouts: Shouts) -> Name {
+fn q7p1(shouts: Shouts) -> Result<Name> {
     let mut all: HashSet<String> = HashSet::new();
     let mut refd: HashSet<String> = HashSet::new();
     for (name, _, supportees) in shouts {
@@ -70,53 +50,57 @@ fn q7p1(shouts: Shouts) -> Name {
     }
     let diff: Vec<&Name> = all.difference(&refd).collect();
     if diff.len() != 1 {
-        panic!("unexpected unreferenced node count {}", diff.len())
+        return Err(Error::unsolvable(format!("unexpected unreferenced node count {}", diff.len())));
     }
-    (*diff.first().unwrap()).to_owned()
+    Ok((*diff.first().unwrap()).to_owned())
 }
 
 struct Summary {
     pub shouts: Shouts,
     // X weighs Y
     pub weights: HashMap<Name, i64>,
-    // X supports [Y]
-    pub supports: HashMap<Name, HashSet<Name>>,
     // X's subtree weighs y
     pub stackweights: HashMap<Name, i64>,
 }
 
 fn summarize(shouts: Shouts) -> Result<Summary> {
-    // X supports {Ys}
-    let mut supports: HashMap<Name, HashSet<Name>> = HashMap::new();
     // X weighs y
-    let mut weights: HashMap<Name, i64> = HashMap::new();
-    for (name, weight, supportees) in shouts.iter().cloned() {
-        weights.insert(name.clone(), weight);
-        for supportee in supportees.iter() {
-            merge(&mut supports, name.clone(), supportee.to_owned());
-        }
+    let weights: HashMap<Name, i64> = shouts.iter().map(|&(ref name, weight, _)| (name.clone(), weight)).collect();
+
+    if weights.len() < shouts.len() {
+        let mut seen = HashSet::new();
+        let dup = shouts.iter().find(|s| !seen.insert(&s.0)).unwrap();
+        return Err(Error::invalid(format!("program {} is defined twice", dup.0)));
     }
 
     // X's subtree weights y
     let mut stackweights: HashMap<Name, i64> = HashMap::new();
     while stackweights.len() < shouts.len() {
+        let before = stackweights.len();
         for (name, weight, supportees) in shouts.iter().cloned() {
             let substack_weight = supportees.iter().map(|child| {
                 stackweights.get(child)
-            }).fold(Some(0), |acc, child_weight| {
-                add_opts(acc, child_weight.cloned())
+            }).try_fold(0, |acc, child_weight| {
+                add_opts(Some(acc), child_weight.cloned())
             });
             if let Some(substack_weight) = substack_weight {
                 stackweights.insert(name, weight + substack_weight);
             }
         }
+        if stackweights.len() == before {
+            // No program's children all have weights, so one is missing
+            // or they support each other in a cycle.
+            return Err(match shouts.iter().flat_map(|s| s.2.iter()).find(|c| !weights.contains_key(*c)) {
+                Some(name) => Error::invalid(format!("program {} is never defined", name)),
+                None => Error::invalid("programs support each other in a cycle"),
+            });
+        }
     }
 
     Ok(Summary{
-        shouts: shouts,
-        weights: weights,
-        supports: supports,
-        stackweights: stackweights,
+        shouts,
+        weights,
+        stackweights,
     })
 }
 
@@ -127,6 +111,16 @@ enum BalancedResult {
 
 use BalancedResult::*;
 
+impl Summary {
+    // Each child's stack weight, paired with the child.
+    fn child_weights(&self, children: Vec<Name>) -> Result<Vec<(i64, Name)>> {
+        children.into_iter().map(|child| match self.stackweights.get(&child) {
+            Some(&w) => Ok((w, child)),
+            None => Err(Error::invalid(format!("no stack weight for {}", child))),
+        }).collect()
+    }
+}
+
 fn is_balanced(z: &Summary) -> Result<BalancedResult> {
     let mut checked = HashSet::new();
     while checked.len() < z.shouts.len() {
@@ -135,7 +129,7 @@ fn is_balanced(z: &Summary) -> Result<BalancedResult> {
                 // Already checked
                 continue
             }
-            if supportees.len() == 0 {
+            if supportees.is_empty() {
                 // Leaf node is auto-checked
                 checked.insert(name);
                 continue
@@ -144,7 +138,7 @@ fn is_balanced(z: &Summary) -> Result<BalancedResult> {
                 // Not all children have been checked yet
                 continue
             }
-            let h: Vec<Group<i64,Name>> = group_by(supportees, |s| z.stackweights[s]);
+            let h: Vec<Group<i64,(i64,Name)>> = group_by(z.child_weights(supportees)?, |c| c.0);
             if h.len() == 1 {
                 // Balanced
                 checked.insert(name);
@@ -156,16 +150,16 @@ fn is_balanced(z: &Summary) -> Result<BalancedResult> {
     Ok(Balanced)
 }
 
-#[allow(dead_code)]
 fn q7p2(shouts: Shouts) -> Result<i64> {
     let z = summarize(shouts)?;
 
     for (name, substack_weight) in z.stackweights.iter() {
-        println!("stackweight {}: {}", name, substack_weight);
+        trace!("stackweight {}: {}", name, substack_weight);
     }
 
-    if let Balanced = is_balanced(&z)? {
-        return e("tree is already balanced");
+    match is_balanced(&z)? {
+        Balanced => return Err(Error::unsolvable("tree is already balanced")),
+        Unbalanced(name) => debug!("first unbalanced program: {}", name),
     }
 
     let mut checked = HashSet::new();
@@ -175,7 +169,7 @@ fn q7p2(shouts: Shouts) -> Result<i64> {
                 // Already checked
                 continue
             }
-            if supportees.len() == 0 {
+            if supportees.is_empty() {
                 // Leaf node is auto-checked
                 checked.insert(name);
                 continue
@@ -184,62 +178,34 @@ fn q7p2(shouts: Shouts) -> Result<i64> {
                 // Not all children have been checked yet
                 continue
             }
-            let h: Vec<Group<i64,Name>> = group_by(supportees, |s| z.stackweights[s]);
-            println!("hist {}: {:?}", name, h);
+            let h: Vec<Group<i64,(i64,Name)>> = group_by(z.child_weights(supportees)?, |c| c.0);
+            trace!("hist {}: {:?}", name, h);
             if h.len() == 1 {
                 // Balanced
                 checked.insert(name);
                 continue
             }
-            println!("unbalanced: {}", name);
+            debug!("unbalanced: {}", name);
             if h.len() != 2 {
-                return Err("too many mismatches".to_owned());
+                return Err(Error::unsolvable("too many mismatches"));
             }
-            let correctee = &h[0].values[0];
+            let correctee = &h[0].values[0].1;
             let delta = h[1].key - h[0].key;
-            let new_weight = z.weights[correctee] + delta;
-            println!("correcting '{}' to {}", correctee, new_weight);
+            let weight = z.weights.get(correctee)
+                .ok_or_else(|| Error::invalid(format!("no weight for {}", correctee)))?;
+            let new_weight = weight + delta;
+            debug!("correcting '{}' to {}", correctee, new_weight);
             return Ok(new_weight);
         }
     }
 
-    return Err("no imbalances detected".to_owned())
-}
-
-/// Add `val` to the the set at `map[key]`
-fn merge<K,V>(map: &mut HashMap<K, HashSet<V>>, key: K, val: V)
-    where K: Eq + Hash,
-          V: Eq + Hash
-{
-    if let Some(set) = map.get_mut(&key) {
-        set.insert(val);
-        return;
-    }
-    let mut set = HashSet::new();
-    set.insert(val);
-    map.insert(key, set);
+    Err(Error::invalid("no imbalances detected"))
 }
 
 fn add_opts(a: Option<i64>, b: Option<i64>) -> Option<i64> {
     a.and_then(|x| b.map(|y| x + y))
 }
 
-/// Create a histogram of value frequency frequency.
-/// Returns a list of number of grouped items, sorted by ascending count.
-#[allow(dead_code)]
-fn histogram<T,I>(items: I) -> Vec<(T, usize)>
-    where T: Eq + Hash + Clone,
-          I: IntoIterator<Item=T>
-{
-    let counts = items.into_iter().fold(HashMap::new(), |mut acc, x| {
-        *acc.entry(x).or_insert(0) += 1;
-        acc
-    });
-    let mut res: Vec<(T, usize)> = counts.iter().map(|(v, c)| (v.clone(), c.clone())).collect();
-    res.sort_by_key(|&(_, count)| count);
-    res
-}
-
 #[derive(Debug)]
 struct Group<K,V> {
     pub key: K,
@@ -270,3 +236,34 @@ fn group_by<K,V,I,F>(items: I, mut key: F) -> Vec<Group<K,V>>
     res.sort_by_key(|xs| xs.values.len());
     res
 }
+
+#[cfg(test)]
+const TOWER: &str = "pbga (66)
+xhth (57)
+ebii (61)
+havc (66)
+ktlj (57)
+fwft (72) -> ktlj, cntj, xhth
+qoyq (66)
+padx (45) -> pbga, havc, qoyq
+tknk (41) -> ugml, padx, fwft
+jptl (61)
+ugml (68) -> gyxo, ebii, jptl
+gyxo (61)
+cntj (57)";
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        (TOWER, 1, "tknk"),
+        (TOWER, 2, "60"),
+    ]);
+}
+
+#[test]
+fn malformed_towers() {
+    let err = |s: &str| q7p2(parse(s).unwrap()).unwrap_err().to_string();
+    assert_eq!("invalid state: program b is never defined", err("a (1) -> b"));
+    assert_eq!("invalid state: programs support each other in a cycle", err("a (1) -> b\nb (1) -> a"));
+    assert_eq!("invalid state: program a is defined twice", err("a (1)\na (2)"));
+}
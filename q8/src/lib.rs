@@ -1,7 +1,9 @@
+#[macro_use]
+extern crate aoc;
+
 use std::fmt;
 use std::collections::{HashMap};
-
-type Result<T> = std::result::Result<T, String>;
+use aoc::{Day, Error, Result};
 
 type RegisterName = String;
 
@@ -65,7 +67,7 @@ impl State {
     }
 
     fn get(&self, r: &RegisterName) -> i64 {
-        self.registers.get(r).map(|x| *x).unwrap_or(0)
+        self.registers.get(r).copied().unwrap_or(0)
     }
 
     fn set(&mut self, r: &RegisterName, val: i64) {
@@ -73,93 +75,71 @@ impl State {
     }
 }
 
-fn e<T>(msg: &str) -> Result<T> {
-    return Err(msg.to_owned());
-}
-
-fn main() {
-    if let Err(err) = main2() {
-        eprintln!("Error: {}", err);
-        std::process::exit(1);
-    }
-}
-
-fn main2() -> Result<()> {
-    let intxt = read_file("input.txt")?;
-    let instructions = parse(&intxt)?;
-    let answer = q8p2(instructions)?;
-    println!("{}", answer);
-    Ok(())
-}
-
-fn read_file(path: &str) -> Result<String> {
-    use std::io::Read;
-    let mut file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(err) => return Err(format!("open file ({}): {}", path, err)),
-    };
-    let mut contents = String::new();
-    if let Err(err) = file.read_to_string(&mut contents) {
-        return Err(format!("read file: {}", err));
-    }
-    Ok(contents)
+pub fn day() -> Day {
+    Day::new(8)
+        .part(1, parse, q8p1)
+        .part(2, parse, q8p2)
 }
 
 fn parse(s: &str) -> Result<Vec<Instruction>> {
     let mut res = Vec::new();
-    for line in s.trim().lines() {
-        let mut words = line.trim().split_whitespace();
-        let target = words.next().ok_or_else(|| format!("missing name"))?.to_owned();
-        let op: Op = match words.next().ok_or_else(|| format!("missing weight"))? {
+    for (i, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let end = line.chars().count() + 1;
+        let missing = |what: &str| Error::parse(i + 1, end, format!("missing {}", what));
+        let bad = |word: &str, what: &str| {
+            Error::parse(i + 1, aoc::column(line, word), format!("{}: {}", what, word))
+        };
+        let mut words = line.split_whitespace();
+        let target = words.next().ok_or_else(|| missing("name"))?.to_owned();
+        let op: Op = match words.next().ok_or_else(|| missing("op"))? {
             "inc" => Inc,
             "dec" => Dec,
-            s => return e(&format!("unrecognized op: {}", s)),
+            s => return Err(bad(s, "unrecognized op")),
         };
-        let operand: i64 = words.next()
-            .ok_or_else(|| format!("missing operand"))?
-            .parse()
-            .map_err(|_| "unexpected non-integer: {}")?;
+        let operand = words.next().ok_or_else(|| missing("operand"))?;
+        let operand: i64 = operand.parse()
+            .map_err(|_| bad(operand, "unexpected non-integer"))?;
         let _if = words.next();
+        let source = words.next().ok_or_else(|| missing("condition source"))?;
+        let comparator = match words.next().ok_or_else(|| missing("comparator"))? {
+            ">" => Gt,
+            "<" => Lt,
+            ">=" => Ge,
+            "<=" => Le,
+            "==" => Eq,
+            "!=" => Neq,
+            s => return Err(bad(s, "unrecognized comparator")),
+        };
+        let cmp_operand = words.next().ok_or_else(|| missing("comparator operand"))?;
         let condition = Condition{
-            source: words.next()
-                .ok_or_else(|| "missing condition source")?.to_owned(),
-            comparator: match words.next().ok_or_else(|| "missing comparator")? {
-                ">" => Gt,
-                "<" => Lt,
-                ">=" => Ge,
-                "<=" => Le,
-                "==" => Eq,
-                "!=" => Neq,
-                s => return e(&format!("unrecognized comparator: {}", s)),
-            },
-            operand: words.next()
-                .ok_or_else(|| "missing comparator operand")?
-                .parse()
-                .map_err(|_| "unexpected non-integer")?,
+            source: source.to_owned(),
+            comparator,
+            operand: cmp_operand.parse()
+                .map_err(|_| bad(cmp_operand, "unexpected non-integer"))?,
         };
         res.push(Instruction{
-            target: target,
-            op: op,
-            operand: operand,
-            condition: condition,
+            target,
+            op,
+            operand,
+            condition,
         });
     }
     Ok(res)
 }
 
 // Maximum register value at the end.
-#[allow(dead_code)]
 fn q8p1(instructions: Vec<Instruction>) -> Result<i64> {
     for instruction in instructions.iter() {
-        println!("{}", instruction)
+        trace!("{}", instruction)
     }
     let state = simulate(instructions);
-    state.registers.values().max()
-        .map(|x| *x).ok_or_else(|| "no registers".to_owned())
+    state.registers.values().max().copied().ok_or_else(|| Error::unsolvable("no registers"))
 }
 
 // Maximum register value at any point.
-#[allow(dead_code)]
 fn q8p2(instructions: Vec<Instruction>) -> Result<i64> {
     let mut max = 0;
     let mut state = State::new();
@@ -176,7 +156,7 @@ fn q8p2(instructions: Vec<Instruction>) -> Result<i64> {
 
 fn simulate(instructions: Vec<Instruction>) -> State {
     instructions.iter().fold(State::new(), |mut state, ins| {
-        step(&mut state, &ins);
+        step(&mut state, ins);
         state
     })
 }
@@ -206,3 +186,17 @@ fn comparate(a: i64, comparator: &Comparator, b: i64) -> bool {
         Neq => a != b,
     }
 }
+
+#[cfg(test)]
+const PROGRAM: &str = "b inc 5 if a > 1
+a inc 1 if b < 5
+c dec -10 if a >= 1
+c inc -20 if c == 10";
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        (PROGRAM, 1, "1"),
+        (PROGRAM, 2, "10"),
+    ]);
+}
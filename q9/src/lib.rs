@@ -1,54 +1,29 @@
+#[macro_use]
+extern crate aoc;
+
 use std::fmt;
 use std::borrow::Borrow;
+use aoc::{Day, Error, Result};
 
-type Result<T> = std::result::Result<T, String>;
-
-#[allow(dead_code)]
-fn e<T>(msg: &str) -> Result<T> {
-    return Err(msg.to_owned());
-}
-
-fn main() {
-    if let Err(err) = main2() {
-        eprintln!("Error: {}", err);
-        std::process::exit(1);
-    }
-}
-
-fn main2() -> Result<()> {
-    let intxt = read_file("input.txt")?;
-    let instructions = parse(&intxt)?;
-    let answer = q9p2(instructions)?;
-    println!("{}", answer);
-    Ok(())
-}
-
-fn read_file(path: &str) -> Result<String> {
-    use std::io::Read;
-    let mut file = match std::fs::File::open(path) {
-        Ok(f) => f,
-        Err(err) => return Err(format!("open file ({}): {}", path, err)),
-    };
-    let mut contents = String::new();
-    if let Err(err) = file.read_to_string(&mut contents) {
-        return Err(format!("read file: {}", err));
-    }
-    Ok(contents)
+pub fn day() -> Day {
+    Day::new(9)
+        .part(1, parse, q9p1)
+        .part(2, parse, q9p2)
 }
 
 struct Group {
-    items: Vec<Box<LI>>,
+    items: Vec<LI>,
 }
 
 impl Group {
     fn new() -> Self { Self{ items: Vec::new() } }
 
     fn push_group(&mut self, g: Group) {
-        self.items.push(Box::new(LI::Group(g)));
+        self.items.push(LI::Group(g));
     }
 
     fn push_junk(&mut self, j: Junk) {
-        self.items.push(Box::new(LI::Junk(j)));
+        self.items.push(LI::Junk(j));
     }
 }
 
@@ -81,8 +56,8 @@ enum LI {
 impl fmt::Debug for LI {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            &LI::Group(ref g) => write!(f, "{:?}", g),
-            &LI::Junk(ref j) => write!(f, "{:?}", j),
+            LI::Group(g) => write!(f, "{:?}", g),
+            LI::Junk(j) => write!(f, "{:?}", j),
         }
     }
 }
@@ -93,6 +68,9 @@ struct Parser {
     stack: Vec<Group>,
     cancel: bool,
     done: bool,
+    // position of the last pushed character
+    line: usize,
+    column: usize,
 }
 
 impl Parser {
@@ -102,36 +80,54 @@ impl Parser {
             stack: Vec::new(),
             cancel: false,
             done: false,
+            line: 1,
+            column: 0,
         }
     }
 
     pub fn push(&mut self, c: char) -> Result<()> {
-        if c == ' ' {
-            // skip spaces
-            return Ok(());
-        }
-        if let Some(top) = self.top.take() {
-            match top {
-                LI::Group(g) => self.push_in_group(c, g),
-                LI::Junk(j)  => self.push_in_junk(c, j),
-            }
-        } else {
-            self.push_empty(c)
+        self.column += 1;
+        let res = self.push_char(c);
+        if c == '\n' {
+            self.line += 1;
+            self.column = 0;
         }
+        res
     }
 
     pub fn finish(self) -> Result<LI> {
         if !self.done {
-            return e("not done");
+            return Err(self.err("not done"));
         }
         let n = self.stack.len();
         if n > 0 {
-            return e(&format!("finished with {} open groups", n));
+            return Err(self.err(format!("finished with {} open groups", n)));
+        }
+        match self.top {
+            Some(top) => Ok(top),
+            None => Err(self.err("no parsed items")),
         }
-        if let Some(top) = self.top {
-            Ok(top)
+    }
+
+    fn err<S>(&self, msg: S) -> Error
+        where S: Into<String>
+    {
+        Error::parse(self.line, self.column, msg)
+    }
+
+    fn push_char(&mut self, c: char) -> Result<()> {
+        let in_junk = matches!(self.top, Some(LI::Junk(_)));
+        if c.is_whitespace() && !in_junk {
+            // skip whitespace between items; it counts inside junk
+            return Ok(());
+        }
+        if let Some(top) = self.top.take() {
+            match top {
+                LI::Group(g) => self.push_in_group(c, g),
+                LI::Junk(j)  => self.push_in_junk(c, j),
+            }
         } else {
-            e("no parsed items")
+            self.push_empty(c)
         }
     }
 
@@ -143,7 +139,7 @@ impl Parser {
             '<' => {
                 self.top = Some(LI::Junk(Junk::new()));
             },
-            c => return e(&format!("unexpected '{}'", c))
+            c => return Err(self.err(format!("unexpected '{}'", c)))
         };
         Ok(())
     }
@@ -162,7 +158,7 @@ impl Parser {
                 } else {
                     self.top = Some(LI::Group(g));
                     if self.done {
-                        return e("closed group but no groups open")
+                        return Err(self.err("closed group but no groups open"))
                     }
                     self.done = true
                 }
@@ -175,7 +171,7 @@ impl Parser {
                 // Ignore commas. Too lazy to make sure they're correct.
                 self.top = Some(LI::Group(g));
             }
-            c => return e(&format!("unexpected character: {}", c))
+            c => return Err(self.err(format!("unexpected character: {}", c)))
             // {{<ab>},{},{<ab>,{<!!>}},{<ab>}}
         };
         Ok(())
@@ -199,7 +195,7 @@ impl Parser {
                     parent.push_junk(j);
                     self.top = Some(LI::Group(parent));
                 } else {
-                    return e("junk ended outside of group");
+                    return Err(self.err("junk ended outside of group"));
                 }
             }
             _ => {
@@ -213,30 +209,31 @@ impl Parser {
 
 fn parse(s: &str) -> Result<Group> {
     let mut p = Parser::new();
-    for c in s.trim().chars() {
+    for c in s.trim_end().chars() {
         p.push(c)?;
         // println!("{} {:?}", c, p);
     }
+    // finish consumes the parser, so keep where it stopped.
+    let (line, column) = (p.line, p.column);
     p.finish().and_then(|x| match x {
         LI::Group(g) => Ok(g),
-        LI::Junk(_) => e("expected group but parsed junk")
+        LI::Junk(_) => Err(Error::parse(line, column, "expected group but parsed junk")),
     })
 }
 
 // Maximum register value at the end.
-#[allow(dead_code)]
 fn q9p1(g: Group) -> Result<i64> {
-    println!("{:?}", g);
+    trace!("{:?}", g);
     Ok(score(&g, 1))
 }
 
 fn q9p2(g: Group) -> Result<i64> {
-    println!("{:?}", g);
+    trace!("{:?}", g);
     Ok(countjunk(&LI::Group(g)))
 }
 
 fn score(g: &Group, depth: i64) -> i64 {
-    let sub: i64 = g.items.iter().filter_map(|g2| match **g2 {
+    let sub: i64 = g.items.iter().filter_map(|g2| match *g2 {
         LI::Group(ref g2) => Some(score(g2, depth+1)),
         LI::Junk(_) => None,
     }).sum();
@@ -247,8 +244,37 @@ fn countjunk<T>(item: &T) -> i64
     where T: Borrow<LI>
 {
     match item.borrow() {
-        &LI::Group(ref g) => g.items.iter().map(countjunk).sum(),
-        &LI::Junk(ref j) => j.count,
+        LI::Group(g) => g.items.iter().map(countjunk).sum(),
+        LI::Junk(j) => j.count,
     }
 }
 
+#[test]
+fn whitespace() {
+    // Whitespace between items is skipped but inside garbage it counts.
+    day().assert_examples(&[
+        ("{ <a b> ,\n<c\td> }\n", 1, "1"),
+        ("{ <a b> ,\n<c\td> }\n", 2, "6"),
+    ]);
+}
+
+#[test]
+fn examples() {
+    day().assert_examples(&[
+        ("{}", 1, "1"),
+        ("{{{}}}", 1, "6"),
+        ("{{},{}}", 1, "5"),
+        ("{{{},{},{{}}}}", 1, "16"),
+        ("{<a>,<a>,<a>,<a>}", 1, "1"),
+        ("{{<ab>},{<ab>},{<ab>},{<ab>}}", 1, "9"),
+        ("{{<!!>},{<!!>},{<!!>},{<!!>}}", 1, "9"),
+        ("{{<a!>},{<a!>},{<a!>},{<ab>}}", 1, "3"),
+        ("{<>}", 2, "0"),
+        ("{<random characters>}", 2, "17"),
+        ("{<<<<>}", 2, "3"),
+        ("{<{!>}>}", 2, "2"),
+        ("{<!!>}", 2, "0"),
+        ("{<!!!>>}", 2, "0"),
+        ("{<{o\"i!a,<{i<a>}", 2, "10"),
+    ]);
+}
@@ -0,0 +1,83 @@
+use aoc::{Error, Input, Result};
+use aoc::log::Level;
+
+use USAGE;
+
+/// How results are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    /// Tab separated values with a header row.
+    Tsv,
+    /// One JSON object per line.
+    Json,
+}
+
+/// Options shared by the subcommands.
+#[derive(Debug)]
+pub struct Options {
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+    pub input: Option<Input>,
+    /// Run every day instead of just `day`.
+    pub all: bool,
+    pub runs: usize,
+    pub format: Format,
+    pub record: bool,
+    pub level: Level,
+}
+
+impl Options {
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let mut opts = Options{
+            day: None,
+            part: None,
+            input: None,
+            all: false,
+            runs: 10,
+            format: Format::Text,
+            record: false,
+            level: Level::Info,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => opts.part = Some(number(args.next())?),
+                "--input" => opts.input = Some(Input::from_arg(value(args.next())?)),
+                "--inline" => opts.input = Some(Input::Inline(value(args.next())?.to_owned())),
+                "--all" => opts.all = true,
+                "--runs" => opts.runs = number(args.next())? as usize,
+                "--format" => opts.format = match value(args.next())? {
+                    "text" => Format::Text,
+                    "tsv" => Format::Tsv,
+                    "json" => Format::Json,
+                    x => return Err(Error::usage(format!("unknown format: {}", x))),
+                },
+                "--record" => opts.record = true,
+                "-q" | "--quiet" => opts.level = Level::Quiet,
+                "-v" => opts.level = Level::Debug,
+                "-vv" => opts.level = Level::Trace,
+                _ if opts.day.is_none() => opts.day = Some(number(Some(arg))?),
+                x => return Err(Error::usage(format!("unexpected argument: {}\n{}", x, USAGE))),
+            }
+        }
+        if opts.runs == 0 {
+            return Err(Error::usage("--runs must be at least 1"));
+        }
+        Ok(opts)
+    }
+
+    /// The input to use for `day`.
+    pub fn input_for(&self, day: u32) -> Input {
+        self.input.clone().unwrap_or_else(|| Input::default_for(day))
+    }
+}
+
+fn value(arg: Option<&String>) -> Result<&str> {
+    arg.map(|s| s.as_str()).ok_or_else(|| Error::usage(USAGE))
+}
+
+fn number(arg: Option<&String>) -> Result<u32> {
+    let arg = value(arg)?;
+    arg.parse().map_err(|_| Error::usage(format!("expected a number: {}", arg)))
+}
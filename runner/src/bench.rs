@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use aoc::{Day, Error, Json, Part, Result, Stats};
+
+use args::{Format, Options};
+
+/// Run each selected part `opts.runs` times and report timing statistics.
+pub fn bench(selected: &[(&Day, Vec<&Part>)], opts: &Options) -> Result<()> {
+    if opts.format == Format::Tsv {
+        println!("day\tpart\truns\tparse_min_ns\tparse_median_ns\tparse_max_ns\tsolve_min_ns\tsolve_median_ns\tsolve_max_ns");
+    }
+    for &(day, ref parts) in selected {
+        let input = match opts.input_for(day.number).load() {
+            Ok(input) => input,
+            Err(Error::Io{..}) if opts.day.is_none() => continue,
+            Err(err) => return Err(err),
+        };
+        for part in parts {
+            let mut parse_times = Vec::with_capacity(opts.runs);
+            let mut solve_times = Vec::with_capacity(opts.runs);
+            for _ in 0..opts.runs {
+                let run = part.run(&input)?;
+                parse_times.push(run.parse_time);
+                solve_times.push(run.solve_time);
+            }
+            let parse = Stats::of(&parse_times);
+            let solve = Stats::of(&solve_times);
+            match opts.format {
+                Format::Text => println!(
+                    "day {:2} part {}: parse {}, solve {} (min / median / max of {})",
+                    day.number, part.number, parse, solve, opts.runs),
+                Format::Tsv => println!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    day.number, part.number, opts.runs,
+                    ns(parse.min), ns(parse.median), ns(parse.max),
+                    ns(solve.min), ns(solve.median), ns(solve.max)),
+                Format::Json => println!("{}", Json::object(vec![
+                    ("day", Json::from(day.number)),
+                    ("part", Json::from(part.number)),
+                    ("runs", Json::from(opts.runs)),
+                    ("parse_ms", stats_json(&parse)),
+                    ("solve_ms", stats_json(&solve)),
+                ])),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn ns(d: Duration) -> u128 {
+    d.as_nanos()
+}
+
+fn stats_json(s: &Stats) -> Json {
+    let ms = |d: Duration| Json::from(d.as_secs_f64() * 1e3);
+    Json::object(vec![
+        ("min", ms(s.min)),
+        ("median", ms(s.median)),
+        ("max", ms(s.max)),
+    ])
+}
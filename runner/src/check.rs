@@ -0,0 +1,55 @@
+use aoc::{Answers, Day, Error, Input, Part, Result};
+
+pub const ANSWERS_PATH: &str = "answers.txt";
+
+/// Run the selected days and parts against their default inputs and compare
+/// with the recorded answers. Returns whether everything passed.
+///
+/// With `record`, answers for parts that have none yet are saved.
+pub fn check(selected: &[(&Day, Vec<&Part>)], record: bool) -> Result<bool> {
+    let mut answers = Answers::load(ANSWERS_PATH)?;
+    let (mut passed, mut failed, mut new) = (0, 0, 0);
+    for &(day, ref parts) in selected {
+        let input = match Input::default_for(day.number).load() {
+            Ok(input) => input,
+            Err(Error::Io{..}) => {
+                println!("day {:2}: no input", day.number);
+                continue;
+            },
+            Err(err) => return Err(err),
+        };
+        let digest = aoc::digest(&input);
+        for part in parts {
+            let label = format!("day {:2} part {}", day.number, part.number);
+            let got = match part.run(&input) {
+                Ok(run) => run.answer.to_string(),
+                Err(err) => {
+                    println!("{}: FAIL {}", label, err);
+                    failed += 1;
+                    continue;
+                },
+            };
+            match answers.get(day.number, part.number, &digest).map(|s| s.to_owned()) {
+                Some(ref want) if *want == got => {
+                    println!("{}: pass", label);
+                    passed += 1;
+                },
+                Some(want) => {
+                    println!("{}: FAIL expected {}, got {}", label, want, got);
+                    failed += 1;
+                },
+                None => {
+                    println!("{}: new {}", label, got);
+                    answers.insert(day.number, part.number, &digest, got);
+                    new += 1;
+                },
+            }
+        }
+    }
+    println!("{} passed, {} failed, {} new", passed, failed, new);
+    if record && new > 0 {
+        answers.save(ANSWERS_PATH)?;
+        println!("recorded {} new answers in {}", new, ANSWERS_PATH);
+    }
+    Ok(failed == 0)
+}
@@ -0,0 +1,241 @@
+#[macro_use]
+extern crate aoc;
+extern crate q1;
+extern crate q2;
+extern crate q3;
+extern crate q4;
+extern crate q5;
+extern crate q6;
+extern crate q7;
+extern crate q8;
+extern crate q9;
+extern crate q10;
+extern crate q11;
+extern crate q12;
+extern crate q13;
+
+mod args;
+mod bench;
+mod check;
+mod pool;
+mod table;
+
+use std::time::{Duration, Instant};
+
+use aoc::{Day, Error, Json, Part, Result, Run, fmt_duration};
+
+use args::{Format, Options};
+
+const USAGE: &str = "usage: aoc run <day> [--part <n>] [--input <path>|- | --inline <text>] [--format text|tsv|json]
+       aoc run --all [--part <n>] [--format text|tsv|json]
+       aoc bench [<day>] [--part <n>] [--input ...] [--runs <n>] [--format text|tsv|json]
+       aoc check [<day>] [--part <n>] [--record]
+       aoc <tool> <day> [args...]   day specific tools, e.g. aoc viz 3 --rings 4
+diagnostics go to stderr: -q for none, -v for debug, -vv for trace";
+
+fn main() {
+    match main2() {
+        Ok(true) => {},
+        Ok(false) => std::process::exit(1),
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            std::process::exit(1);
+        },
+    }
+}
+
+/// Returns whether the command succeeded.
+fn main2() -> Result<bool> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = args.first().map(|s| s.as_str());
+    let rest = args.get(1..).unwrap_or(&[]);
+    let days = days();
+    if let Some(name) = command.filter(|c| !["run", "bench", "check"].contains(c)) {
+        return tool(&days, name, rest);
+    }
+    let opts = Options::parse(rest)?;
+    aoc::log::set_level(opts.level);
+    match command {
+        Some("run") => {
+            if opts.day.is_some() == opts.all {
+                return Err(Error::usage(USAGE));
+            }
+            if opts.all && opts.input.is_some() {
+                return Err(Error::usage("--all always reads each day's own input"));
+            }
+            run(&select(&days, &opts)?, &opts)
+        },
+        Some("bench") => bench::bench(&select(&days, &opts)?, &opts).map(|_| true),
+        Some("check") => check::check(&select(&days, &opts)?, opts.record),
+        _ => Err(Error::usage(USAGE)),
+    }
+}
+
+/// Run a day's tool, as in `aoc viz 3 --rings 4`.
+fn tool(days: &[Day], name: &str, args: &[String]) -> Result<bool> {
+    let n: u32 = args.first().and_then(|s| s.parse().ok()).ok_or_else(|| Error::usage(USAGE))?;
+    let day = days.iter().find(|d| d.number == n)
+        .ok_or_else(|| Error::usage(format!("no such day: {}", n)))?;
+    let tool = day.find_tool(name)
+        .ok_or_else(|| Error::usage(format!("day {} has no tool {}\n{}", n, name, USAGE)))?;
+    print!("{}", tool.run(&args[1..])?);
+    Ok(true)
+}
+
+/// Every registered day.
+fn days() -> Vec<Day> {
+    vec![
+        q1::day(),
+        q2::day(),
+        q3::day(),
+        q4::day(),
+        q5::day(),
+        q6::day(),
+        q7::day(),
+        q8::day(),
+        q9::day(),
+        q10::day(),
+        q11::day(),
+        q12::day(),
+        q13::day(),
+    ]
+}
+
+/// The days and parts picked out by `--day` and `--part`, defaulting to all.
+fn select<'a>(days: &'a [Day], opts: &Options) -> Result<Vec<(&'a Day, Vec<&'a Part>)>> {
+    let mut res = Vec::new();
+    for day in days.iter().filter(|d| opts.day.is_none_or(|n| d.number == n)) {
+        let parts: Vec<&Part> = day.parts().iter()
+            .filter(|p| opts.part.is_none_or(|n| p.number == n))
+            .collect();
+        if parts.is_empty() {
+            return Err(Error::usage(format!("day {} has no part {}", day.number, opts.part.unwrap_or(0))));
+        }
+        res.push((day, parts));
+    }
+    if res.is_empty() {
+        return Err(Error::usage(format!("no such day: {}", opts.day.unwrap_or(0))));
+    }
+    Ok(res)
+}
+
+/// Returns whether every part succeeded.
+/// With `--all` the parts run concurrently and text output is a summary table.
+fn run(selected: &[(&Day, Vec<&Part>)], opts: &Options) -> Result<bool> {
+    let mut ok = true;
+    let mut inputs = Vec::new();
+    let mut missing = Vec::new();
+    for &(day, ref parts) in selected {
+        match opts.input_for(day.number).load() {
+            Ok(input) => inputs.push((day, parts, input)),
+            // --all skips days that have no input yet.
+            Err(Error::Io{..}) if opts.all => {
+                debug!("day {}: no input", day.number);
+                missing.push(day);
+            },
+            Err(ref err) if opts.format == Format::Json => {
+                println!("{}", Json::object(vec![
+                    ("day", Json::from(day.number)),
+                    ("error", Json::from(err)),
+                ]));
+                ok = false;
+            },
+            Err(err) => return Err(err),
+        }
+    }
+    let digests: Vec<String> = inputs.iter().map(|x| aoc::digest(&x.2)).collect();
+    let mut jobs = Vec::new();
+    for (&(day, parts, ref input), digest) in inputs.iter().zip(digests.iter()) {
+        for &part in parts.iter() {
+            jobs.push((day, part, input.as_str(), digest.as_str()));
+        }
+    }
+    let threads = if opts.all { pool::threads() } else { 1 };
+    let start = Instant::now();
+    let runs = pool::map(&jobs, threads, |&(_, part, input, _)| part.run(input));
+    let wall = start.elapsed();
+
+    if opts.all && opts.format == Format::Text {
+        return Ok(summary(&jobs, &runs, &missing, wall, threads) && ok);
+    }
+    if opts.format == Format::Tsv {
+        println!("day\tpart\tanswer\tparse_ms\tsolve_ms\tinput_digest");
+    }
+    for (&(day, part, _, digest), run) in jobs.iter().zip(runs) {
+        let run = match run {
+            Ok(run) => run,
+            Err(ref err) if opts.format == Format::Json => {
+                println!("{}", Json::object(vec![
+                    ("day", Json::from(day.number)),
+                    ("part", Json::from(part.number)),
+                    ("error", Json::from(err)),
+                    ("input_digest", Json::from(digest)),
+                ]));
+                ok = false;
+                continue;
+            },
+            Err(ref err) if opts.all => {
+                eprintln!("Error: day {} part {}: {}", day.number, part.number, err);
+                ok = false;
+                continue;
+            },
+            Err(err) => return Err(err),
+        };
+        match opts.format {
+            Format::Text => {
+                println!("{}", run.answer);
+                info!("day {} part {}: parse {}, solve {}",
+                      day.number, part.number, fmt_duration(run.parse_time), fmt_duration(run.solve_time));
+            },
+            Format::Tsv => println!("{}\t{}\t{}\t{}\t{}\t{}",
+                                    day.number, part.number, run.answer,
+                                    ms(run.parse_time), ms(run.solve_time), digest),
+            Format::Json => println!("{}", Json::object(vec![
+                ("day", Json::from(day.number)),
+                ("part", Json::from(part.number)),
+                ("answer", Json::from(&run.answer)),
+                ("parse_ms", Json::from(ms(run.parse_time))),
+                ("solve_ms", Json::from(ms(run.solve_time))),
+                ("input_digest", Json::from(digest)),
+            ])),
+        }
+    }
+    Ok(ok)
+}
+
+/// Print the `run --all` table. Returns whether every part succeeded.
+fn summary(jobs: &[(&Day, &Part, &str, &str)], runs: &[Result<Run>], missing: &[&Day],
+           wall: Duration, threads: usize) -> bool {
+    let mut rows: Vec<(u32, Vec<String>)> = Vec::new();
+    let mut failed = 0;
+    for (&(day, part, _, _), run) in jobs.iter().zip(runs) {
+        let cells = match *run {
+            Ok(ref run) => vec![run.answer.to_string(), fmt_duration(run.parse_time), fmt_duration(run.solve_time)],
+            Err(ref err) => {
+                failed += 1;
+                vec![format!("error: {}", err), String::new(), String::new()]
+            },
+        };
+        let mut row = vec![day.number.to_string(), part.number.to_string()];
+        row.extend(cells);
+        rows.push((day.number, row));
+    }
+    for day in missing {
+        rows.push((day.number, vec![day.number.to_string(), "-".to_owned(), "no input".to_owned()]));
+    }
+    rows.sort_by_key(|&(n, _)| n);
+    let rows: Vec<Vec<String>> = rows.into_iter().map(|(_, row)| row).collect();
+    table::print(&["day", "part", "answer", "parse", "solve"], &rows, &[0, 1, 3, 4]);
+    println!("{}: {} ok, {} failed, {} without input ({} on {})",
+             plural(jobs.len(), "part"), jobs.len() - failed, failed,
+             plural(missing.len(), "day"), fmt_duration(wall), plural(threads, "thread"));
+    failed == 0
+}
+
+fn plural(n: usize, noun: &str) -> String {
+    format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" })
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1e3
+}
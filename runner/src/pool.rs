@@ -0,0 +1,48 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Apply `f` to every item on up to `threads` worker threads.
+/// Results come back in the same order as `items`.
+pub fn map<T,R,F>(items: &[T], threads: usize, f: F) -> Vec<R>
+    where T: Sync,
+          R: Send,
+          F: Fn(&T) -> R + Sync
+{
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let next = &next;
+            let f = &f;
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= items.len() {
+                    break;
+                }
+                // The receiver outlives the scope, so this cannot fail.
+                let _ = tx.send((i, f(&items[i])));
+            });
+        }
+    });
+    drop(tx);
+    let mut res: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    for (i, r) in rx {
+        res[i] = Some(r);
+    }
+    res.into_iter().map(|r| r.expect("pool lost a result")).collect()
+}
+
+/// A sensible worker count for this machine.
+pub fn threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+#[test]
+fn keeps_order() {
+    let xs: Vec<u64> = (0..100).collect();
+    let ys = map(&xs, 4, |x| x * x);
+    assert_eq!(xs.iter().map(|x| x * x).collect::<Vec<_>>(), ys);
+    assert!(map(&Vec::<u64>::new(), 4, |x| *x).is_empty());
+}
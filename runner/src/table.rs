@@ -0,0 +1,38 @@
+/// Print `rows` under `header` with every column padded to its widest cell.
+/// Columns whose index is in `right` are right aligned.
+pub fn print(header: &[&str], rows: &[Vec<String>], right: &[usize]) {
+    let header: Vec<String> = header.iter().map(|&h| h.to_owned()).collect();
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            let n = cell.chars().count();
+            if i >= widths.len() {
+                widths.push(n);
+            } else if n > widths[i] {
+                widths[i] = n;
+            }
+        }
+    }
+    for row in Some(&header).into_iter().chain(rows) {
+        println!("{}", line(row, &widths, right));
+    }
+}
+
+fn line(row: &[String], widths: &[usize], right: &[usize]) -> String {
+    let cells: Vec<String> = row.iter().enumerate().map(|(i, cell)| {
+        if right.contains(&i) {
+            format!("{:>1$}", cell, widths[i])
+        } else {
+            format!("{:<1$}", cell, widths[i])
+        }
+    }).collect();
+    cells.join("  ").trim_end().to_owned()
+}
+
+#[test]
+fn aligns() {
+    let row = |xs: &[&str]| xs.iter().map(|&x| x.to_owned()).collect::<Vec<_>>();
+    let widths = [3, 5, 2];
+    assert_eq!("  1  ab     x", line(&row(&["1", "ab", "x"]), &widths, &[0]));
+    assert_eq!("day  part", line(&row(&["day", "part", ""]), &widths, &[0, 2]));
+}